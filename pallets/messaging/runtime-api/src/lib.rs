@@ -0,0 +1,44 @@
+//! # Secura Messaging Runtime API
+//!
+//! Read-only messaging queries for clients, answered by the messaging pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    /// Messaging queries exposed by the runtime.
+    pub trait SecuraMessagingApi<AccountId, Hash, BlockNumber, Message>
+    where
+        AccountId: Codec,
+        Hash: Codec,
+        BlockNumber: Codec,
+        Message: Codec,
+    {
+        /// A group thread as `(message_id, parent_id)` pairs, rooted at `root_id`.
+        fn group_thread(group_id: Hash, root_id: Hash) -> Vec<(Hash, Option<Hash>)>;
+
+        /// Ids of the messages `sender` has posted to a group, oldest first.
+        fn group_messages_by_sender(group_id: Hash, sender: AccountId) -> Vec<Hash>;
+
+        /// Group a message was posted to, `None` for direct or unknown messages.
+        fn message_group(message_id: Hash) -> Option<Hash>;
+
+        /// `message_id` followed by the messages it was forwarded from, ending with the
+        /// original.
+        fn forward_chain(message_id: Hash) -> Vec<Hash>;
+
+        /// Inbox and outbox messages of `account` sent between `from` and `to` inclusive,
+        /// oldest first.
+        fn messages_in_range(account: AccountId, from: BlockNumber, to: BlockNumber) -> Vec<Hash>;
+
+        /// Up to `count` inbox messages of `account` from position `start`, with contents.
+        fn inbox_page(account: AccountId, start: u32, count: u32) -> Vec<(Hash, Message)>;
+
+        /// Up to `count` outbox messages of `account` from position `start`, with contents.
+        fn outbox_page(account: AccountId, start: u32, count: u32) -> Vec<(Hash, Message)>;
+    }
+}
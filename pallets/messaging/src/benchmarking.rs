@@ -12,7 +12,7 @@ mod benchmarks {
     fn send_message() {
         let caller: T::AccountId = whitelisted_caller();
         let recipient: T::AccountId = account("recipient", 0, 0);
-        let content_cid = vec![0u8; 32]; // 32 bytes IPFS CID
+        let content_cid = vec![vec![0u8; 32]]; // One 32 bytes IPFS CID
         
         #[extrinsic_call]
         send_message(RawOrigin::Signed(caller), recipient, content_cid);
@@ -22,7 +22,7 @@ mod benchmarks {
     fn read_message() {
         let sender: T::AccountId = account("sender", 0, 0);
         let recipient: T::AccountId = whitelisted_caller();
-        let content_cid = vec![0u8; 32]; // 32 bytes IPFS CID
+        let content_cid = vec![vec![0u8; 32]]; // One 32 bytes IPFS CID
         
         // Setup: Send a message first
         let _ = Pallet::<T>::send_message(
@@ -45,7 +45,7 @@ mod benchmarks {
     fn delete_message() {
         let sender: T::AccountId = account("sender", 0, 0);
         let recipient: T::AccountId = whitelisted_caller();
-        let content_cid = vec![0u8; 32]; // 32 bytes IPFS CID
+        let content_cid = vec![vec![0u8; 32]]; // One 32 bytes IPFS CID
         
         // Setup: Send a message first
         let _ = Pallet::<T>::send_message(
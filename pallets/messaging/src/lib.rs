@@ -13,7 +13,7 @@ pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::Get};
+    use frame_support::{pallet_prelude::*, traits::{Currency, Get, ReservableCurrency}};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use crate::weights::WeightInfo;
@@ -21,21 +21,92 @@ pub mod pallet {
     use frame_support::sp_runtime::traits::Hash;
     use frame_support::sp_runtime::Saturating;
 
+    type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// Version 1 stores messages with their group, reply and forwarding links and a list of
+    /// content CIDs, and groups with their admins. There is no migration from version 0:
+    /// chains holding version 0 state must start again from a fresh genesis.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         
+        /// Currency the storage deposits are reserved in
+        type Currency: ReservableCurrency<Self::AccountId>;
+        
+        /// Deposit reserved from the sender for each stored message
+        #[pallet::constant]
+        type MessageDeposit: Get<BalanceOf<Self>>;
+        
+        /// Deposit reserved from the owner for each group
+        #[pallet::constant]
+        type GroupDeposit: Get<BalanceOf<Self>>;
+        
         /// Maximum message length
         #[pallet::constant]
         type MaxMessageLength: Get<u32>;
         
+        /// Maximum length of the content CID of a group message
+        #[pallet::constant]
+        type MaxGroupCidLength: Get<u32>;
+        
+        /// Maximum number of content CIDs attached to a single message
+        #[pallet::constant]
+        type MaxAttachments: Get<u32>;
+        
+        /// Maximum number of messages in an inbox
+        #[pallet::constant]
+        type MaxInboxSize: Get<u32>;
+        
+        /// Maximum number of messages in an outbox
+        #[pallet::constant]
+        type MaxOutboxSize: Get<u32>;
+        
+        /// Maximum number of messages stored across all accounts and groups
+        #[pallet::constant]
+        type MaxTotalMessages: Get<u32>;
+        
+        /// Maximum number of members of a group, owner included
+        #[pallet::constant]
+        type MaxGroupMembers: Get<u32>;
+        
+        /// Maximum number of groups an account can belong to
+        #[pallet::constant]
+        type MaxGroupsPerUser: Get<u32>;
+        
+        /// Maximum number of messages a group keeps
+        #[pallet::constant]
+        type MaxGroupMessages: Get<u32>;
+        
+        /// Maximum number of reactions a single message can hold
+        #[pallet::constant]
+        type MaxReactionsPerMessage: Get<u32>;
+        
+        /// Maximum number of forwarding hops `forward_chain` follows
+        #[pallet::constant]
+        type MaxForwardDepth: Get<u32>;
+        
         /// Message time-to-live in blocks
         #[pallet::constant]
         type MessageTTL: Get<BlockNumberFor<Self>>;
         
+        /// Shortest time-to-live a sender can set on a message
+        #[pallet::constant]
+        type MinMessageTTL: Get<BlockNumberFor<Self>>;
+        
+        /// Longest time-to-live a sender can set on a message
+        #[pallet::constant]
+        type MaxMessageTTL: Get<BlockNumberFor<Self>>;
+        
+        /// Furthest ahead of the current block a message can be scheduled for delivery
+        #[pallet::constant]
+        type MaxScheduleDelay: Get<BlockNumberFor<Self>>;
+        
         /// Weight information
         type WeightInfo: WeightInfo;
     }
@@ -46,7 +117,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         T::Hash,
-        Message<T::AccountId, BlockNumberFor<T>>,
+        MessageOf<T>,
     >;
 
     #[pallet::storage]
@@ -55,7 +126,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         T::AccountId,
-        BoundedVec<T::Hash, ConstU32<100>>,
+        BoundedVec<T::Hash, T::MaxInboxSize>,
         ValueQuery,
     >;
 
@@ -65,21 +136,61 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         T::AccountId,
-        BoundedVec<T::Hash, ConstU32<100>>,
+        BoundedVec<T::Hash, T::MaxOutboxSize>,
+        ValueQuery,
+    >;
+
+    // Number of unread direct messages per recipient
+    #[pallet::storage]
+    #[pallet::getter(fn unread_count)]
+    pub type UnreadCount<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
         ValueQuery,
     >;
     
+    // Number of messages currently stored
+    #[pallet::storage]
+    #[pallet::getter(fn total_message_count)]
+    pub type TotalMessageCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+    
+    // Deposit reserved from the sender of each stored message
+    #[pallet::storage]
+    #[pallet::getter(fn message_deposits)]
+    pub type MessageDeposits<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Message ID
+        BalanceOf<T>,
+    >;
+    
     // Group definition
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
-    pub struct Group<AccountId> {
+    #[scale_info(skip_type_params(MaxMembers))]
+    #[codec(mel_bound(AccountId: MaxEncodedLen))]
+    pub struct Group<AccountId, MaxMembers: Get<u32>> {
         // Group owner
         pub owner: AccountId,
         // Group members
-        pub members: BoundedVec<AccountId, ConstU32<50>>,
+        pub members: BoundedVec<AccountId, MaxMembers>,
+        // Members other than the owner who may add and remove members
+        pub admins: BoundedVec<AccountId, MaxMembers>,
         // Group name
         pub name: BoundedVec<u8, ConstU32<32>>,
     }
 
+    impl<AccountId: PartialEq, MaxMembers: Get<u32>> Group<AccountId, MaxMembers> {
+        /// Whether `who` is the owner or an admin of the group
+        pub fn is_admin(&self, who: &AccountId) -> bool {
+            self.owner == *who || self.admins.contains(who)
+        }
+    }
+
+    /// A group as stored by the pallet
+    pub type GroupOf<T> = Group<<T as frame_system::Config>::AccountId, <T as Config>::MaxGroupMembers>;
+
     // Storage for groups
     #[pallet::storage]
     #[pallet::getter(fn groups)]
@@ -87,7 +198,17 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         T::Hash,  // Group ID
-        Group<T::AccountId>,
+        GroupOf<T>,
+    >;
+
+    // Deposit reserved for each group and the account it is refunded to
+    #[pallet::storage]
+    #[pallet::getter(fn group_deposits)]
+    pub type GroupDeposits<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Group ID
+        (T::AccountId, BalanceOf<T>),  // (Depositor, deposit)
     >;
 
     // Group membership index
@@ -97,7 +218,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         T::AccountId,
-        BoundedVec<T::Hash, ConstU32<50>>,  // Groups the user belongs to
+        BoundedVec<T::Hash, T::MaxGroupsPerUser>,  // Groups the user belongs to
         ValueQuery,
     >;
 
@@ -108,10 +229,134 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         T::Hash,  // Group ID
+        BoundedVec<T::Hash, T::MaxGroupMessages>,  // Message IDs
+        ValueQuery,
+    >;
+
+    // Messages expiring at each block
+    #[pallet::storage]
+    #[pallet::getter(fn expiry_index)]
+    pub type ExpiryIndex<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BlockNumberFor<T>,
         BoundedVec<T::Hash, ConstU32<1000>>,  // Message IDs
         ValueQuery,
     >;
 
+    // Reactions to each message, at most one reaction code per reactor
+    #[pallet::storage]
+    #[pallet::getter(fn reactions)]
+    pub type Reactions<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Message ID
+        BoundedVec<(T::AccountId, u32), T::MaxReactionsPerMessage>,  // (Reactor, reaction code)
+        ValueQuery,
+    >;
+
+    // Senders each recipient refuses direct messages from
+    #[pallet::storage]
+    #[pallet::getter(fn blocked_senders)]
+    pub type BlockedSenders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,  // Recipient
+        Blake2_128Concat,
+        T::AccountId,  // Sender
+        (),
+        OptionQuery,
+    >;
+
+    // Group activity summary
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo, Default)]
+    pub struct GroupActivity<BlockNumber> {
+        // Number of messages currently in the group
+        pub message_count: u32,
+        // Block of the latest message posted to the group
+        pub last_activity: BlockNumber,
+    }
+
+    // Storage for group activity
+    #[pallet::storage]
+    #[pallet::getter(fn group_stats)]
+    pub type GroupStats<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Group ID
+        GroupActivity<BlockNumberFor<T>>,
+        ValueQuery,
+    >;
+
+    // Group messages index per sender
+    #[pallet::storage]
+    #[pallet::getter(fn group_sender_messages)]
+    pub type GroupSenderMessages<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Group ID
+        Blake2_128Concat,
+        T::AccountId,  // Sender
+        BoundedVec<T::Hash, T::MaxGroupMessages>,  // Message IDs
+        ValueQuery,
+    >;
+
+    // Index of the latest group message each member has seen
+    #[pallet::storage]
+    #[pallet::getter(fn group_read_cursor)]
+    pub type GroupReadCursor<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Group ID
+        Blake2_128Concat,
+        T::AccountId,  // Member
+        u32,  // Position in GroupMessages
+    >;
+
+    // A direct message waiting for its delivery block
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+    pub struct ScheduledMessage<AccountId, BlockNumber> {
+        // Sender
+        pub sender: AccountId,
+        // Recipient
+        pub recipient: AccountId,
+        // IPFS CID of encrypted content
+        pub content_cid: BoundedVec<u8, ConstU32<64>>,
+        // Block at which the message is delivered
+        pub deliver_at: BlockNumber,
+    }
+
+    // Storage for scheduled messages
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_messages)]
+    pub type ScheduledMessages<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Schedule ID
+        ScheduledMessage<T::AccountId, BlockNumberFor<T>>,
+    >;
+
+    // Deposit reserved from the sender of each scheduled message
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_deposits)]
+    pub type ScheduledDeposits<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,  // Schedule ID
+        BalanceOf<T>,
+    >;
+
+    // Scheduled messages due at each block
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_deliveries)]
+    pub type ScheduledDeliveries<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BlockNumberFor<T>,
+        BoundedVec<T::Hash, ConstU32<50>>,  // Schedule IDs
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -119,6 +364,8 @@ pub mod pallet {
         MessageSent(T::Hash, T::AccountId, T::AccountId),
         /// Message read [message_id, recipient]
         MessageRead(T::Hash, T::AccountId),
+        /// Direct message read for the first time, keyed by its sender [message_id, sender, reader]
+        MessageReadReceipt(T::Hash, T::AccountId, T::AccountId),
         /// Message deleted [message_id]
         MessageDeleted(T::Hash),
         /// Group created [group_id, owner]
@@ -127,8 +374,36 @@ pub mod pallet {
         MemberAdded(T::Hash, T::AccountId),
         /// Member removed from group [group_id, member]
         MemberRemoved(T::Hash, T::AccountId),
+        /// Member made a group admin [group_id, admin]
+        AdminAdded(T::Hash, T::AccountId),
+        /// Group admin demoted to a regular member [group_id, admin]
+        AdminRemoved(T::Hash, T::AccountId),
         /// Group message sent [message_id, group_id, sender]
         GroupMessageSent(T::Hash, T::Hash, T::AccountId),
+        /// Member has seen group messages up to an index [group_id, member, index]
+        GroupReadCursorUpdated(T::Hash, T::AccountId, u32),
+        /// Message sent as a reply [message_id, parent_id]
+        ReplySent(T::Hash, T::Hash),
+        /// Message forwarded to a new recipient [message_id, forwarded_from, original_id]
+        MessageForwarded(T::Hash, T::Hash, T::Hash),
+        /// Message scheduled for later delivery [schedule_id, sender, recipient, deliver_at]
+        MessageScheduled(T::Hash, T::AccountId, T::AccountId, BlockNumberFor<T>),
+        /// Scheduled message cancelled before delivery [schedule_id]
+        ScheduledMessageCancelled(T::Hash),
+        /// Message removed after its expiry block [message_id]
+        MessageExpired(T::Hash),
+        /// Batch of messages marked as read [count]
+        MessagesRead(u32),
+        /// Reaction added or changed [message_id, reactor, reaction]
+        ReactionAdded(T::Hash, T::AccountId, u32),
+        /// Reaction removed [message_id, reactor]
+        ReactionRemoved(T::Hash, T::AccountId),
+        /// Message time-to-live changed [message_id, expires_at]
+        MessageTtlUpdated(T::Hash, BlockNumberFor<T>),
+        /// Group handed to another member [group_id, old_owner, new_owner]
+        GroupOwnershipTransferred(T::Hash, T::AccountId, T::AccountId),
+        /// Group and its messages removed [group_id]
+        GroupDeleted(T::Hash),
     }
 
     #[pallet::error]
@@ -151,28 +426,108 @@ pub mod pallet {
         NotGroupMember,
         /// Not the group owner
         NotGroupOwner,
+        /// Neither the group owner nor an admin
+        NotGroupAdmin,
+        /// Member is already a group admin
+        AlreadyAdmin,
         /// Group is full
         GroupFull,
         /// User is already a member
         AlreadyMember,
         /// User is in too many groups
         TooManyGroups,
+        /// No group message at the given index
+        InvalidMessageIndex,
+        /// Replied-to message does not belong to the group
+        ParentNotInGroup,
+        /// Scheduled message not found
+        ScheduledMessageNotFound,
+        /// Delivery block is not in the future or is more than `MaxScheduleDelay` ahead
+        InvalidDeliveryBlock,
+        /// Too many messages scheduled for the same block
+        TooManyScheduled,
+        /// Too many messages expiring at the same block
+        ExpiryIndexFull,
+        /// The group's message log is full
+        GroupMessageLogFull,
+        /// Message already holds the maximum number of reactions
+        TooManyReactions,
+        /// Message has already expired
+        MessageAlreadyExpired,
+        /// New time-to-live would expire the message in the past
+        InvalidTtl,
+        /// Recipient does not accept messages from the sender
+        SenderBlocked,
+        /// Message has more content CIDs than `MaxAttachments`
+        TooManyAttachments,
+        /// Free balance does not cover the storage deposit
+        InsufficientBalance,
+        /// `MaxTotalMessages` messages are already stored
+        MessageStorageFull,
+        /// Every id derived for the new message is taken by a stored message
+        DuplicateMessageId,
+        /// Every id derived for the new group is taken by a stored group
+        DuplicateGroupId,
+        /// Every id derived for the new scheduled message is taken
+        DuplicateScheduleId,
+    }
+
+    /// Storage bound of a message's content CID, covering both direct messages (at most 64
+    /// bytes) and group messages (at most `MaxGroupCidLength` bytes)
+    #[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+    pub struct MaxCidLength<T>(PhantomData<T>);
+
+    impl<T: Config> Get<u32> for MaxCidLength<T> {
+        fn get() -> u32 {
+            T::MaxGroupCidLength::get().max(64)
+        }
     }
 
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
-    pub struct Message<AccountId, BlockNumber> {
+    #[scale_info(skip_type_params(MaxCid, MaxAttachments))]
+    #[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen, Hash: MaxEncodedLen))]
+    pub struct Message<AccountId, BlockNumber, Hash, MaxCid: Get<u32>, MaxAttachments: Get<u32>> {
         /// Sender
         pub sender: AccountId,
         /// Recipient
         pub recipient: AccountId,
-        /// IPFS CID of encrypted content
-        pub content_cid: BoundedVec<u8, ConstU32<64>>,
+        /// IPFS CIDs of the encrypted content, one per attachment
+        pub content_cid: BoundedVec<BoundedVec<u8, MaxCid>, MaxAttachments>,
         /// When sent
         pub timestamp: BlockNumber,
         /// When expires
         pub expires_at: BlockNumber,
         /// Read status
         pub read: bool,
+        /// Group the message was posted to, `None` for direct messages
+        pub group_id: Option<Hash>,
+        /// Message this one replies to, if any
+        pub reply_to: Option<Hash>,
+        /// Message this one was forwarded from, if any
+        pub forwarded_from: Option<Hash>,
+        /// First message of the forwarding chain, `None` unless forwarded
+        pub original_id: Option<Hash>,
+    }
+
+    /// A message as stored by the pallet
+    pub type MessageOf<T> = Message<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as frame_system::Config>::Hash,
+        MaxCidLength<T>,
+        <T as Config>::MaxAttachments,
+    >;
+
+    /// Content CIDs of a message as stored by the pallet
+    pub type ContentOf<T> = BoundedVec<BoundedVec<u8, MaxCidLength<T>>, <T as Config>::MaxAttachments>;
+
+    impl<AccountId, BlockNumber, Hash, MaxCid: Get<u32>, MaxAttachments: Get<u32>>
+        Message<AccountId, BlockNumber, Hash, MaxCid, MaxAttachments>
+    {
+        /// Whether the message was posted to a group rather than sent directly
+        pub fn is_group_message(&self) -> bool {
+            self.group_id.is_some()
+        }
     }
 
     #[pallet::call]
@@ -183,51 +538,67 @@ pub mod pallet {
         pub fn send_message(
             origin: OriginFor<T>,
             recipient: T::AccountId,
-            content_cid: Vec<u8>,
+            content_cid: Vec<Vec<u8>>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
-            // Check CID size
-            ensure!(content_cid.len() <= 64, Error::<T>::InvalidCID);
+            Self::do_send_message(sender, recipient, content_cid, None)?;
             
-            let bounded_cid = BoundedVec::<u8, ConstU32<64>>::try_from(content_cid)
-                .map_err(|_| Error::<T>::InvalidCID)?;
+            Ok(())
+        }
+        
+        /// Send a direct message replying to an earlier message the caller took part in
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::send_reply())]
+        pub fn send_reply(
+            origin: OriginFor<T>,
+            recipient: T::AccountId,
+            content_cid: Vec<Vec<u8>>,
+            parent_id: T::Hash,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
             
-            let now = frame_system::Pallet::<T>::block_number();
-            let expires_at = now.saturating_add(T::MessageTTL::get());
+            let parent = Messages::<T>::get(parent_id).ok_or(Error::<T>::MessageNotFound)?;
+            ensure!(
+                parent.sender == sender || parent.recipient == sender,
+                Error::<T>::NotAuthorized
+            );
             
-            // Create message
-            let message = Message {
-                sender: sender.clone(),
-                recipient: recipient.clone(),
-                content_cid: bounded_cid,
-                timestamp: now,
-                expires_at,
-                read: false,
-            };
+            let message_id = Self::do_send_message(sender, recipient, content_cid, Some(parent_id))?;
             
-            // Generate ID
-            let message_id = <<T as frame_system::Config>::Hashing as Hash>::hash_of(&(
-                &sender,
-                &recipient,
-                &now
-            ));
+            Self::deposit_event(Event::ReplySent(message_id, parent_id));
             
-            // Store message
-            Messages::<T>::insert(message_id, message);
+            Ok(())
+        }
+        
+        /// Forward a message the caller sent or received to `recipient` as a new direct message
+        /// that links back to it. Only its author can forward a group message
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::forward_message())]
+        pub fn forward_message(
+            origin: OriginFor<T>,
+            message_id: T::Hash,
+            recipient: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
             
-            // Update recipient's inbox
-            Inbox::<T>::try_mutate(&recipient, |messages| {
-                messages.try_push(message_id)
-            }).map_err(|_| Error::<T>::InboxFull)?;
+            let message = Messages::<T>::get(message_id).ok_or(Error::<T>::MessageNotFound)?;
+            ensure!(
+                message.sender == sender || message.recipient == sender,
+                Error::<T>::NotAuthorized
+            );
             
-            // Update sender's outbox
-            Outbox::<T>::try_mutate(&sender, |messages| {
-                messages.try_push(message_id)
-            }).map_err(|_| Error::<T>::OutboxFull)?;
+            // The CIDs were bounded when the message was sent, a group's longer ones included
+            let original_id = message.original_id.unwrap_or(message_id);
+            let forwarded_id = Self::do_send_bounded_message(sender, recipient, message.content_cid, None)?;
+            Messages::<T>::mutate(forwarded_id, |maybe_message| {
+                if let Some(forwarded) = maybe_message {
+                    forwarded.forwarded_from = Some(message_id);
+                    forwarded.original_id = Some(original_id);
+                }
+            });
             
-            // Emit event
-            Self::deposit_event(Event::MessageSent(message_id, sender, recipient));
+            Self::deposit_event(Event::MessageForwarded(forwarded_id, message_id, original_id));
             
             Ok(())
         }
@@ -241,13 +612,18 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             
-            Messages::<T>::try_mutate(message_id, |maybe_message| -> DispatchResult {
+            let receipt = Messages::<T>::try_mutate(message_id, |maybe_message| -> Result<Option<T::AccountId>, DispatchError> {
                 let message = maybe_message.as_mut().ok_or(Error::<T>::MessageNotFound)?;
                 ensure!(message.recipient == who, Error::<T>::NotAuthorized);
                 
+                let newly_read = !message.read && !message.is_group_message();
                 message.read = true;
-                Ok(())
+                Ok(newly_read.then(|| message.sender.clone()))
             })?;
+            if let Some(sender) = receipt {
+                Self::decrement_unread(&who);
+                Self::deposit_event(Event::MessageReadReceipt(message_id, sender, who.clone()));
+            }
             
             Self::deposit_event(Event::MessageRead(message_id, who));
             
@@ -271,6 +647,11 @@ pub mod pallet {
             
             // Remove message
             Messages::<T>::remove(message_id);
+            Reactions::<T>::remove(message_id);
+            Self::note_message_removed(message_id, &message.sender);
+            if !message.read && !message.is_group_message() {
+                Self::decrement_unread(&message.recipient);
+            }
             
             // Clean up inbox/outbox
             if message.recipient == who {
@@ -289,6 +670,21 @@ pub mod pallet {
                 });
             }
             
+            // Group messages are also referenced from the group indexes
+            if let Some(group_id) = message.group_id {
+                Self::remove_group_references(group_id, &message.sender, message_id);
+            }
+            
+            // Nothing left to expire
+            ExpiryIndex::<T>::mutate_exists(message.expires_at, |maybe_ids| {
+                if let Some(ids) = maybe_ids {
+                    ids.retain(|id| *id != message_id);
+                    if ids.is_empty() {
+                        *maybe_ids = None;
+                    }
+                }
+            });
+            
             Self::deposit_event(Event::MessageDeleted(message_id));
             
             Ok(())
@@ -310,7 +706,7 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::MessageTooLong)?;
             
             // Validate members count
-            ensure!(initial_members.len() < 50, Error::<T>::GroupFull);
+            ensure!(initial_members.len() < T::MaxGroupMembers::get() as usize, Error::<T>::GroupFull);
             
             // Create unique member list including owner
             let mut members = Vec::with_capacity(initial_members.len() + 1);
@@ -321,22 +717,28 @@ pub mod pallet {
                 }
             }
             
-            let bounded_members = BoundedVec::<T::AccountId, ConstU32<50>>::try_from(members)
+            let bounded_members = BoundedVec::<T::AccountId, T::MaxGroupMembers>::try_from(members)
                 .map_err(|_| Error::<T>::GroupFull)?;
             
             // Create group
             let group = Group {
                 owner: owner.clone(),
                 members: bounded_members.clone(),
+                admins: BoundedVec::new(),
                 name: bounded_name.clone(),
             };
             
-            // Generate group ID
-            let group_id = <<T as frame_system::Config>::Hashing as Hash>::hash_of(&(
-                &owner,
-                &bounded_name,
-                &frame_system::Pallet::<T>::block_number()
-            ));
+            // Generate group ID, salted if the owner already created a group of this name in
+            // this block
+            let group_id = Self::unique_id(
+                &(&owner, &bounded_name, frame_system::Pallet::<T>::block_number()),
+                |id| Groups::<T>::contains_key(id),
+            ).ok_or(Error::<T>::DuplicateGroupId)?;
+            
+            // Reserve the group deposit, refunded to the owner when the group is deleted
+            let deposit = T::GroupDeposit::get();
+            T::Currency::reserve(&owner, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+            GroupDeposits::<T>::insert(group_id, (owner.clone(), deposit));
             
             // Store group
             Groups::<T>::insert(group_id, group);
@@ -366,8 +768,8 @@ pub mod pallet {
             Groups::<T>::try_mutate(group_id, |maybe_group| -> DispatchResult {
                 let group = maybe_group.as_mut().ok_or(Error::<T>::GroupNotFound)?;
                 
-                // Only owner can add members
-                ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+                // Only the owner and admins can add members
+                ensure!(group.is_admin(&who), Error::<T>::NotGroupAdmin);
                 
                 // Check if already a member
                 ensure!(!group.members.contains(&new_member), Error::<T>::AlreadyMember);
@@ -400,53 +802,700 @@ pub mod pallet {
             Groups::<T>::try_mutate(group_id, |maybe_group| -> DispatchResult {
                 let group = maybe_group.as_mut().ok_or(Error::<T>::GroupNotFound)?;
                 
-                // Only owner can remove members
-                ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+                // Only the owner and admins can remove members
+                ensure!(group.is_admin(&who), Error::<T>::NotGroupAdmin);
                 
                 // Cannot remove owner
                 ensure!(group.owner != member, Error::<T>::NotAuthorized);
                 
-                // Remove from group
-                if let Some(pos) = group.members.iter().position(|m| *m == member) {
-                    group.members.swap_remove(pos);
-                } else {
-                    return Err(Error::<T>::NotGroupMember.into());
-                }
+                ensure!(Self::do_remove_member(group, group_id, member), Error::<T>::NotGroupMember);
                 
-                // Update membership
-                GroupMembership::<T>::mutate(&member, |groups| {
-                    if let Some(pos) = groups.iter().position(|g| *g == group_id) {
-                        groups.swap_remove(pos);
-                    }
-                });
+                Ok(())
+            })
+        }
+        
+        /// Remove several members from a group at once, skipping the owner and accounts that
+        /// are not members
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::remove_member().saturating_mul(members.len() as u64))]
+        pub fn remove_members(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+            members: BoundedVec<T::AccountId, T::MaxGroupMembers>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            Groups::<T>::try_mutate(group_id, |maybe_group| -> DispatchResult {
+                let group = maybe_group.as_mut().ok_or(Error::<T>::GroupNotFound)?;
+                
+                // Only the owner and admins can remove members
+                ensure!(group.is_admin(&who), Error::<T>::NotGroupAdmin);
                 
-                Self::deposit_event(Event::MemberRemoved(group_id, member));
+                for member in members {
+                    if group.owner != member {
+                        Self::do_remove_member(group, group_id, member);
+                    }
+                }
                 
                 Ok(())
             })
         }
         
-        /// Send message to group
-        #[pallet::call_index(6)]
-        #[pallet::weight(T::WeightInfo::send_group_message())]
-        pub fn send_group_message(
+        /// Leave a group. The owner cannot leave its own group.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::remove_member())]
+        pub fn leave_group(
             origin: OriginFor<T>,
             group_id: T::Hash,
-            content_cid: Vec<u8>,
         ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            
-            // Check group exists and sender is a member
-            let group = Groups::<T>::get(group_id).ok_or(Error::<T>::GroupNotFound)?;
-            ensure!(group.members.contains(&sender), Error::<T>::NotGroupMember);
+            let who = ensure_signed(origin)?;
             
-            // Validate CID
-            ensure!(content_cid.len() <= 64, Error::<T>::InvalidCID);
-            let bounded_cid = BoundedVec::<u8, ConstU32<64>>::try_from(content_cid)
-                .map_err(|_| Error::<T>::InvalidCID)?;
+            Groups::<T>::try_mutate(group_id, |maybe_group| -> DispatchResult {
+                let group = maybe_group.as_mut().ok_or(Error::<T>::GroupNotFound)?;
+                
+                // Owners must transfer ownership first
+                ensure!(group.owner != who, Error::<T>::NotAuthorized);
+                
+                ensure!(Self::do_remove_member(group, group_id, who), Error::<T>::NotGroupMember);
+                
+                Ok(())
+            })
+        }
+        
+        /// Let a member add and remove members. Only the owner may appoint admins.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::add_admin())]
+        pub fn add_admin(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+            admin: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
             
-            let now = frame_system::Pallet::<T>::block_number();
-            let expires_at = now.saturating_add(T::MessageTTL::get());
+            Groups::<T>::try_mutate(group_id, |maybe_group| -> DispatchResult {
+                let group = maybe_group.as_mut().ok_or(Error::<T>::GroupNotFound)?;
+                
+                ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+                ensure!(group.members.contains(&admin), Error::<T>::NotGroupMember);
+                ensure!(!group.is_admin(&admin), Error::<T>::AlreadyAdmin);
+                
+                // Admins are members, so the member bound always fits
+                group.admins.try_push(admin.clone()).map_err(|_| Error::<T>::GroupFull)?;
+                
+                Self::deposit_event(Event::AdminAdded(group_id, admin));
+                
+                Ok(())
+            })
+        }
+        
+        /// Demote an admin to a regular member. The owner can only hand over the group with
+        /// `transfer_group_ownership`.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::remove_admin())]
+        pub fn remove_admin(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+            admin: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            Groups::<T>::try_mutate(group_id, |maybe_group| -> DispatchResult {
+                let group = maybe_group.as_mut().ok_or(Error::<T>::GroupNotFound)?;
+                
+                ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+                let pos = group.admins.iter().position(|a| *a == admin)
+                    .ok_or(Error::<T>::NotGroupAdmin)?;
+                
+                group.admins.swap_remove(pos);
+                
+                Self::deposit_event(Event::AdminRemoved(group_id, admin));
+                
+                Ok(())
+            })
+        }
+        
+        /// Hand a group over to one of its members
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::add_member())]
+        pub fn transfer_group_ownership(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            Groups::<T>::try_mutate(group_id, |maybe_group| -> DispatchResult {
+                let group = maybe_group.as_mut().ok_or(Error::<T>::GroupNotFound)?;
+                
+                ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+                ensure!(group.members.contains(&new_owner), Error::<T>::NotGroupMember);
+                
+                // The owner is an admin by right, not by listing
+                group.admins.retain(|admin| *admin != new_owner);
+                group.owner = new_owner.clone();
+                
+                Self::deposit_event(Event::GroupOwnershipTransferred(group_id, who, new_owner));
+                
+                Ok(())
+            })
+        }
+        
+        /// Delete a group along with its memberships and messages. Only the owner may do
+        /// this. Bounded by `MaxGroupMembers` and `MaxGroupMessages`.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::delete_group())]
+        pub fn delete_group(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let group = Groups::<T>::get(group_id).ok_or(Error::<T>::GroupNotFound)?;
+            ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+            
+            Groups::<T>::remove(group_id);
+            if let Some((depositor, deposit)) = GroupDeposits::<T>::take(group_id) {
+                T::Currency::unreserve(&depositor, deposit);
+            }
+            
+            // Update membership for all members
+            for member in group.members.iter() {
+                GroupMembership::<T>::mutate(member, |groups| {
+                    if let Some(pos) = groups.iter().position(|g| *g == group_id) {
+                        groups.swap_remove(pos);
+                    }
+                });
+            }
+            
+            // Remove every message posted to the group
+            for message_id in GroupMessages::<T>::take(group_id) {
+                let Some(message) = Messages::<T>::take(message_id) else {
+                    continue;
+                };
+                Reactions::<T>::remove(message_id);
+                Self::note_message_removed(message_id, &message.sender);
+                Outbox::<T>::mutate(&message.sender, |messages| {
+                    if let Some(pos) = messages.iter().position(|id| *id == message_id) {
+                        messages.swap_remove(pos);
+                    }
+                });
+                ExpiryIndex::<T>::mutate_exists(message.expires_at, |maybe_ids| {
+                    if let Some(ids) = maybe_ids {
+                        ids.retain(|id| *id != message_id);
+                        if ids.is_empty() {
+                            *maybe_ids = None;
+                        }
+                    }
+                });
+            }
+            
+            // Sender and cursor indexes hold at most one entry per poster or member
+            let _ = GroupSenderMessages::<T>::clear_prefix(group_id, u32::MAX, None);
+            let _ = GroupReadCursor::<T>::clear_prefix(group_id, u32::MAX, None);
+            GroupStats::<T>::remove(group_id);
+            
+            Self::deposit_event(Event::GroupDeleted(group_id));
+            
+            Ok(())
+        }
+        
+        /// Refuse direct messages from `who`. No event is emitted so the blocked account
+        /// is not notified.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::block_user())]
+        pub fn block_user(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let recipient = ensure_signed(origin)?;
+            
+            BlockedSenders::<T>::insert(&recipient, &who, ());
+            
+            Ok(())
+        }
+        
+        /// Accept direct messages from a previously blocked `who` again
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::unblock_user())]
+        pub fn unblock_user(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let recipient = ensure_signed(origin)?;
+            
+            BlockedSenders::<T>::remove(&recipient, &who);
+            
+            Ok(())
+        }
+        
+        /// Send message to group
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::send_group_message())]
+        pub fn send_group_message(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+            content_cid: Vec<Vec<u8>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            Self::do_send_group_message(sender, group_id, content_cid, None)?;
+            
+            Ok(())
+        }
+
+        /// Reply to an earlier message in the same group
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::send_group_reply())]
+        pub fn send_group_reply(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+            content_cid: Vec<Vec<u8>>,
+            parent_id: T::Hash,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // The parent must be a message of this group
+            let parent = Messages::<T>::get(parent_id).ok_or(Error::<T>::MessageNotFound)?;
+            ensure!(parent.group_id == Some(group_id), Error::<T>::ParentNotInGroup);
+            
+            let message_id = Self::do_send_group_message(sender, group_id, content_cid, Some(parent_id))?;
+            
+            Self::deposit_event(Event::ReplySent(message_id, parent_id));
+            
+            Ok(())
+        }
+
+        /// Schedule a direct message for delivery at a later block
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::schedule_message())]
+        pub fn schedule_message(
+            origin: OriginFor<T>,
+            recipient: T::AccountId,
+            content_cid: Vec<u8>,
+            deliver_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(deliver_at > now, Error::<T>::InvalidDeliveryBlock);
+            ensure!(
+                deliver_at <= now.saturating_add(T::MaxScheduleDelay::get()),
+                Error::<T>::InvalidDeliveryBlock
+            );
+            
+            let bounded_cid = BoundedVec::<u8, ConstU32<64>>::try_from(content_cid)
+                .map_err(|_| Error::<T>::InvalidCID)?;
+            
+            // Generate schedule ID, salted if the pair already scheduled one in this block
+            let schedule_id = Self::unique_id(
+                &(&sender, &recipient, deliver_at, now),
+                |id| ScheduledMessages::<T>::contains_key(id),
+            ).ok_or(Error::<T>::DuplicateScheduleId)?;
+            
+            ScheduledDeliveries::<T>::try_mutate(deliver_at, |ids| {
+                ids.try_push(schedule_id)
+            }).map_err(|_| Error::<T>::TooManyScheduled)?;
+            
+            // The message holds its place in the store and its deposit until it is delivered
+            // or cancelled
+            let count = TotalMessageCount::<T>::get();
+            ensure!(count < T::MaxTotalMessages::get(), Error::<T>::MessageStorageFull);
+            let deposit = T::MessageDeposit::get();
+            T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+            ScheduledDeposits::<T>::insert(schedule_id, deposit);
+            TotalMessageCount::<T>::put(count + 1);
+            
+            ScheduledMessages::<T>::insert(schedule_id, ScheduledMessage {
+                sender: sender.clone(),
+                recipient: recipient.clone(),
+                content_cid: bounded_cid,
+                deliver_at,
+            });
+            
+            Self::deposit_event(Event::MessageScheduled(schedule_id, sender, recipient, deliver_at));
+            
+            Ok(())
+        }
+
+        /// Cancel a scheduled message that has not been delivered yet
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::cancel_scheduled_message())]
+        pub fn cancel_scheduled_message(
+            origin: OriginFor<T>,
+            schedule_id: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Delivered messages are no longer scheduled
+            let scheduled = ScheduledMessages::<T>::get(schedule_id)
+                .ok_or(Error::<T>::ScheduledMessageNotFound)?;
+            ensure!(scheduled.sender == who, Error::<T>::NotAuthorized);
+            
+            ScheduledMessages::<T>::remove(schedule_id);
+            ScheduledDeliveries::<T>::mutate(scheduled.deliver_at, |ids| {
+                ids.retain(|id| *id != schedule_id);
+            });
+            Self::note_schedule_released(schedule_id, &who);
+            
+            Self::deposit_event(Event::ScheduledMessageCancelled(schedule_id));
+            
+            Ok(())
+        }
+
+        /// Mark several direct messages as read at once. Messages that do not exist, are not
+        /// addressed to the caller or are already read are skipped.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::read_messages(message_ids.len() as u32))]
+        pub fn read_messages(
+            origin: OriginFor<T>,
+            message_ids: BoundedVec<T::Hash, ConstU32<100>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let mut count: u32 = 0;
+            for message_id in message_ids {
+                Messages::<T>::mutate(message_id, |maybe_message| {
+                    if let Some(message) = maybe_message {
+                        if message.recipient == who && !message.read && !message.is_group_message() {
+                            message.read = true;
+                            count = count.saturating_add(1);
+                            Self::deposit_event(Event::MessageReadReceipt(
+                                message_id,
+                                message.sender.clone(),
+                                who.clone(),
+                            ));
+                        }
+                    }
+                });
+            }
+            
+            UnreadCount::<T>::mutate_exists(&who, |maybe_count| {
+                *maybe_count = maybe_count.map(|unread| unread.saturating_sub(count)).filter(|unread| *unread > 0);
+            });
+            
+            Self::deposit_event(Event::MessagesRead(count));
+            
+            Ok(())
+        }
+
+        /// React to a message the caller can see. Reacting again with the same code removes
+        /// the reaction, a different code replaces it.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::react())]
+        pub fn react(
+            origin: OriginFor<T>,
+            message_id: T::Hash,
+            reaction: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let message = Messages::<T>::get(message_id).ok_or(Error::<T>::MessageNotFound)?;
+            ensure!(Self::can_see(&message, &who), Error::<T>::NotAuthorized);
+            
+            let added = Reactions::<T>::try_mutate(message_id, |reactions| -> Result<bool, DispatchError> {
+                match reactions.iter().position(|(reactor, _)| *reactor == who) {
+                    Some(pos) if reactions[pos].1 == reaction => {
+                        reactions.remove(pos);
+                        Ok(false)
+                    },
+                    Some(pos) => {
+                        reactions[pos].1 = reaction;
+                        Ok(true)
+                    },
+                    None => {
+                        reactions.try_push((who.clone(), reaction))
+                            .map_err(|_| Error::<T>::TooManyReactions)?;
+                        Ok(true)
+                    },
+                }
+            })?;
+            
+            if added {
+                Self::deposit_event(Event::ReactionAdded(message_id, who, reaction));
+            } else {
+                Self::deposit_event(Event::ReactionRemoved(message_id, who));
+            }
+            
+            Ok(())
+        }
+
+        /// Change how long a sent message lives. `new_ttl` counts from when the message was
+        /// sent and is clamped to `MinMessageTTL..=MaxMessageTTL`.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::set_message_ttl())]
+        pub fn set_message_ttl(
+            origin: OriginFor<T>,
+            message_id: T::Hash,
+            new_ttl: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let mut message = Messages::<T>::get(message_id).ok_or(Error::<T>::MessageNotFound)?;
+            ensure!(message.sender == who, Error::<T>::NotAuthorized);
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(message.expires_at > now, Error::<T>::MessageAlreadyExpired);
+            
+            let ttl = new_ttl.clamp(T::MinMessageTTL::get(), T::MaxMessageTTL::get());
+            let expires_at = message.timestamp.saturating_add(ttl);
+            ensure!(expires_at > now, Error::<T>::InvalidTtl);
+            
+            // Move the message to its new expiry block
+            if expires_at != message.expires_at {
+                ExpiryIndex::<T>::mutate_exists(message.expires_at, |maybe_ids| {
+                    if let Some(ids) = maybe_ids {
+                        ids.retain(|id| *id != message_id);
+                        if ids.is_empty() {
+                            *maybe_ids = None;
+                        }
+                    }
+                });
+                ExpiryIndex::<T>::try_mutate(expires_at, |ids| {
+                    ids.try_push(message_id)
+                }).map_err(|_| Error::<T>::ExpiryIndexFull)?;
+                
+                message.expires_at = expires_at;
+                Messages::<T>::insert(message_id, message);
+            }
+            
+            Self::deposit_event(Event::MessageTtlUpdated(message_id, expires_at));
+            
+            Ok(())
+        }
+
+        /// Mark group messages as seen up to and including `index`
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::update_group_read_cursor())]
+        pub fn update_group_read_cursor(
+            origin: OriginFor<T>,
+            group_id: T::Hash,
+            index: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let group = Groups::<T>::get(group_id).ok_or(Error::<T>::GroupNotFound)?;
+            ensure!(group.members.contains(&who), Error::<T>::NotGroupMember);
+            ensure!(
+                (index as usize) < GroupMessages::<T>::get(group_id).len(),
+                Error::<T>::InvalidMessageIndex
+            );
+            
+            // The cursor only moves forward
+            let cursor = GroupReadCursor::<T>::mutate(group_id, &who, |cursor| {
+                let updated = cursor.map_or(index, |current| current.max(index));
+                *cursor = Some(updated);
+                updated
+            });
+            
+            Self::deposit_event(Event::GroupReadCursorUpdated(group_id, who, cursor));
+            
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        // Salted ids tried for a new entry before giving up on finding a free one
+        const MAX_ID_ATTEMPTS: u32 = 8;
+
+        /// Whether `message_id` refers to a stored group message
+        pub fn is_group_message(message_id: T::Hash) -> bool {
+            Messages::<T>::get(message_id).is_some_and(|message| message.is_group_message())
+        }
+
+        // Bound the CIDs of a direct message and store it
+        fn do_send_message(
+            sender: T::AccountId,
+            recipient: T::AccountId,
+            content_cid: Vec<Vec<u8>>,
+            reply_to: Option<T::Hash>,
+        ) -> Result<T::Hash, DispatchError> {
+            // Check CID sizes
+            let bounded_cid = Self::bound_content(content_cid, 64)?;
+            
+            Self::do_send_bounded_message(sender, recipient, bounded_cid, reply_to)
+        }
+
+        // Store a direct message whose CIDs are already bounded and index it in the recipient's
+        // inbox and sender's outbox
+        fn do_send_bounded_message(
+            sender: T::AccountId,
+            recipient: T::AccountId,
+            bounded_cid: ContentOf<T>,
+            reply_to: Option<T::Hash>,
+        ) -> Result<T::Hash, DispatchError> {
+            ensure!(
+                !BlockedSenders::<T>::contains_key(&recipient, &sender),
+                Error::<T>::SenderBlocked
+            );
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(T::MessageTTL::get());
+            
+            // Create message
+            let message = Message {
+                sender: sender.clone(),
+                recipient: recipient.clone(),
+                content_cid: bounded_cid,
+                timestamp: now,
+                expires_at,
+                read: false,
+                group_id: None,
+                reply_to,
+                forwarded_from: None,
+                original_id: None,
+            };
+            
+            // Generate ID
+            let message_id = Self::unique_message_id(&sender, &recipient, now)?;
+            
+            // Store message and index its expiry
+            Self::note_message_stored(message_id, &sender)?;
+            Messages::<T>::insert(message_id, message);
+            ExpiryIndex::<T>::try_mutate(expires_at, |ids| {
+                ids.try_push(message_id)
+            }).map_err(|_| Error::<T>::ExpiryIndexFull)?;
+            
+            // Update recipient's inbox
+            Inbox::<T>::try_mutate(&recipient, |messages| {
+                messages.try_push(message_id)
+            }).map_err(|_| Error::<T>::InboxFull)?;
+            
+            // Update sender's outbox
+            Outbox::<T>::try_mutate(&sender, |messages| {
+                messages.try_push(message_id)
+            }).map_err(|_| Error::<T>::OutboxFull)?;
+            UnreadCount::<T>::mutate(&recipient, |count| *count = count.saturating_add(1));
+            
+            // Emit event
+            Self::deposit_event(Event::MessageSent(message_id, sender, recipient));
+            
+            Ok(message_id)
+        }
+
+        // Hash `key` into an id not yet `taken`. Entries created from the same key, such as
+        // several in one block, are told apart by a salt, giving up after `MAX_ID_ATTEMPTS`.
+        fn unique_id<Key: Encode>(key: &Key, taken: impl Fn(&T::Hash) -> bool) -> Option<T::Hash> {
+            (0..Self::MAX_ID_ATTEMPTS)
+                .map(|salt| match salt {
+                    0 => <<T as frame_system::Config>::Hashing as Hash>::hash_of(key),
+                    _ => <<T as frame_system::Config>::Hashing as Hash>::hash_of(&(key, salt)),
+                })
+                .find(|id| !taken(id))
+        }
+
+        // Derive a message id from the sender, the recipient or group and the block. Further
+        // messages between the same parties in the same block are salted, so they never
+        // overwrite a stored message and its deposit.
+        fn unique_message_id<Target: Encode>(
+            sender: &T::AccountId,
+            target: &Target,
+            now: BlockNumberFor<T>,
+        ) -> Result<T::Hash, DispatchError> {
+            Self::unique_id(&(sender, target, now), |id| Messages::<T>::contains_key(id))
+                .ok_or_else(|| Error::<T>::DuplicateMessageId.into())
+        }
+
+        // Count a new message against `MaxTotalMessages` and reserve its storage deposit from
+        // the sender. Expired messages are pruned every block, so a full store has nothing
+        // left to clean up and the message is rejected.
+        fn note_message_stored(message_id: T::Hash, sender: &T::AccountId) -> DispatchResult {
+            let count = TotalMessageCount::<T>::get();
+            ensure!(count < T::MaxTotalMessages::get(), Error::<T>::MessageStorageFull);
+            
+            let deposit = T::MessageDeposit::get();
+            T::Currency::reserve(sender, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+            MessageDeposits::<T>::insert(message_id, deposit);
+            TotalMessageCount::<T>::put(count + 1);
+            Ok(())
+        }
+        
+        // Free the store slot held by a scheduled message and refund its deposit to the sender
+        fn note_schedule_released(schedule_id: T::Hash, sender: &T::AccountId) {
+            TotalMessageCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+            if let Some(deposit) = ScheduledDeposits::<T>::take(schedule_id) {
+                T::Currency::unreserve(sender, deposit);
+            }
+        }
+
+        // Free the store slot of a removed message and refund its deposit to the sender
+        fn note_message_removed(message_id: T::Hash, sender: &T::AccountId) {
+            TotalMessageCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+            if let Some(deposit) = MessageDeposits::<T>::take(message_id) {
+                T::Currency::unreserve(sender, deposit);
+            }
+        }
+
+        // Drop `member` from `group` and its membership indexes, returning whether it was a
+        // member
+        fn do_remove_member(group: &mut GroupOf<T>, group_id: T::Hash, member: T::AccountId) -> bool {
+            let Some(pos) = group.members.iter().position(|m| *m == member) else {
+                return false;
+            };
+            group.members.swap_remove(pos);
+            group.admins.retain(|admin| *admin != member);
+            
+            // Update membership
+            GroupMembership::<T>::mutate(&member, |groups| {
+                if let Some(pos) = groups.iter().position(|g| *g == group_id) {
+                    groups.swap_remove(pos);
+                }
+            });
+            GroupReadCursor::<T>::remove(group_id, &member);
+            
+            Self::deposit_event(Event::MemberRemoved(group_id, member));
+            
+            true
+        }
+
+        // Bound the content CIDs of a message, requiring at least one and each at most
+        // `max_len` bytes
+        fn bound_content(content_cid: Vec<Vec<u8>>, max_len: u32) -> Result<ContentOf<T>, DispatchError> {
+            ensure!(!content_cid.is_empty(), Error::<T>::InvalidCID);
+            ensure!(
+                content_cid.len() <= T::MaxAttachments::get() as usize,
+                Error::<T>::TooManyAttachments
+            );
+            
+            let mut bounded = ContentOf::<T>::new();
+            for cid in content_cid {
+                ensure!(cid.len() <= max_len as usize, Error::<T>::InvalidCID);
+                let cid = BoundedVec::try_from(cid).map_err(|_| Error::<T>::InvalidCID)?;
+                bounded.try_push(cid).map_err(|_| Error::<T>::TooManyAttachments)?;
+            }
+            Ok(bounded)
+        }
+
+        // Direct messages are visible to both parties, group messages to the members
+        fn can_see(message: &MessageOf<T>, who: &T::AccountId) -> bool {
+            match message.group_id {
+                Some(group_id) => Groups::<T>::get(group_id).is_some_and(|group| group.members.contains(who)),
+                None => message.sender == *who || message.recipient == *who,
+            }
+        }
+
+        // One less unread direct message for `who`
+        fn decrement_unread(who: &T::AccountId) {
+            UnreadCount::<T>::mutate_exists(who, |maybe_count| {
+                *maybe_count = maybe_count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+            });
+        }
+
+        // Store a message in a group's log on behalf of one of its members
+        fn do_send_group_message(
+            sender: T::AccountId,
+            group_id: T::Hash,
+            content_cid: Vec<Vec<u8>>,
+            reply_to: Option<T::Hash>,
+        ) -> Result<T::Hash, DispatchError> {
+            // Check group exists and sender is a member
+            let group = Groups::<T>::get(group_id).ok_or(Error::<T>::GroupNotFound)?;
+            ensure!(group.members.contains(&sender), Error::<T>::NotGroupMember);
+            
+            // Validate CIDs
+            let bounded_cid = Self::bound_content(content_cid, T::MaxGroupCidLength::get())?;
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(T::MessageTTL::get());
             
             // Create message
             let message = Message {
@@ -456,22 +1505,33 @@ pub mod pallet {
                 timestamp: now,
                 expires_at,
                 read: false,
+                group_id: Some(group_id),
+                reply_to,
+                forwarded_from: None,
+                original_id: None,
             };
             
             // Generate message ID
-            let message_id = <<T as frame_system::Config>::Hashing as Hash>::hash_of(&(
-                &sender,
-                &group_id,
-                &now
-            ));
+            let message_id = Self::unique_message_id(&sender, &group_id, now)?;
             
-            // Store message
+            // Store message and index its expiry
+            Self::note_message_stored(message_id, &sender)?;
             Messages::<T>::insert(message_id, message);
+            ExpiryIndex::<T>::try_mutate(expires_at, |ids| {
+                ids.try_push(message_id)
+            }).map_err(|_| Error::<T>::ExpiryIndexFull)?;
             
             // Add to group messages
             GroupMessages::<T>::try_mutate(group_id, |messages| {
                 messages.try_push(message_id)
-            }).map_err(|_| Error::<T>::InboxFull)?;
+            }).map_err(|_| Error::<T>::GroupMessageLogFull)?;
+            GroupSenderMessages::<T>::try_mutate(group_id, &sender, |messages| {
+                messages.try_push(message_id)
+            }).map_err(|_| Error::<T>::GroupMessageLogFull)?;
+            GroupStats::<T>::mutate(group_id, |stats| {
+                stats.message_count = stats.message_count.saturating_add(1);
+                stats.last_activity = now;
+            });
             
             // Add to sender's outbox
             Outbox::<T>::try_mutate(&sender, |messages| {
@@ -480,15 +1540,249 @@ pub mod pallet {
             
             Self::deposit_event(Event::GroupMessageSent(message_id, group_id, sender));
             
-            Ok(())
+            Ok(message_id)
+        }
+
+        // Drop a group message from the group log, the sender index and the group stats
+        fn remove_group_references(group_id: T::Hash, sender: &T::AccountId, message_id: T::Hash) {
+            Self::remove_from_group_log(group_id, message_id);
+            GroupStats::<T>::mutate(group_id, |stats| {
+                stats.message_count = stats.message_count.saturating_sub(1);
+            });
+            GroupSenderMessages::<T>::mutate_exists(group_id, sender, |maybe_ids| {
+                if let Some(ids) = maybe_ids {
+                    ids.retain(|id| *id != message_id);
+                    if ids.is_empty() {
+                        *maybe_ids = None;
+                    }
+                }
+            });
+        }
+
+        // Remove an expired message along with every index referencing it
+        fn expire_message(message_id: T::Hash) {
+            let Some(message) = Messages::<T>::take(message_id) else {
+                return;
+            };
+            Reactions::<T>::remove(message_id);
+            Self::note_message_removed(message_id, &message.sender);
+            if !message.read && !message.is_group_message() {
+                Self::decrement_unread(&message.recipient);
+            }
+            
+            Inbox::<T>::mutate(&message.recipient, |messages| {
+                if let Some(pos) = messages.iter().position(|id| *id == message_id) {
+                    messages.swap_remove(pos);
+                }
+            });
+            Outbox::<T>::mutate(&message.sender, |messages| {
+                if let Some(pos) = messages.iter().position(|id| *id == message_id) {
+                    messages.swap_remove(pos);
+                }
+            });
+            if let Some(group_id) = message.group_id {
+                Self::remove_group_references(group_id, &message.sender, message_id);
+            }
+            
+            Self::deposit_event(Event::MessageExpired(message_id));
+        }
+
+        // Drop a message from its group log, shifting read cursors so they keep pointing at
+        // the same messages
+        fn remove_from_group_log(group_id: T::Hash, message_id: T::Hash) {
+            let removed = GroupMessages::<T>::mutate(group_id, |messages| {
+                let pos = messages.iter().position(|id| *id == message_id)?;
+                messages.remove(pos);
+                Some(pos as u32)
+            });
+            let Some(removed) = removed else {
+                return;
+            };
+            
+            let cursors: Vec<(T::AccountId, u32)> = GroupReadCursor::<T>::iter_prefix(group_id)
+                .filter(|(_, cursor)| *cursor >= removed)
+                .collect();
+            for (member, cursor) in cursors {
+                // A member who had only seen the removed first message has now seen nothing
+                match cursor.checked_sub(1) {
+                    Some(shifted) => GroupReadCursor::<T>::insert(group_id, &member, shifted),
+                    None => GroupReadCursor::<T>::remove(group_id, &member),
+                }
+            }
+        }
+
+        /// Messages of a group thread as `(message_id, parent_id)` pairs, starting with
+        /// `root_id` and followed by every direct or nested reply to it in posting order.
+        /// Bounded by the size of the group message log.
+        pub fn group_thread(group_id: T::Hash, root_id: T::Hash) -> Vec<(T::Hash, Option<T::Hash>)> {
+            let log = GroupMessages::<T>::get(group_id);
+            if !log.contains(&root_id) {
+                return Vec::new();
+            }
+            
+            let mut thread: Vec<(T::Hash, Option<T::Hash>)> = Vec::new();
+            // Replies are always posted after their parent, so one pass in log order suffices
+            for message_id in log.iter() {
+                let Some(message) = Messages::<T>::get(message_id) else {
+                    continue;
+                };
+                let in_thread = *message_id == root_id
+                    || message.reply_to.is_some_and(|parent| thread.iter().any(|(id, _)| *id == parent));
+                if in_thread {
+                    let parent = if *message_id == root_id { None } else { message.reply_to };
+                    thread.push((*message_id, parent));
+                }
+            }
+            
+            thread
+        }
+
+        /// Messages `sender` has posted to a group, oldest first
+        pub fn group_messages_by_sender(group_id: T::Hash, sender: T::AccountId) -> Vec<T::Hash> {
+            GroupSenderMessages::<T>::get(group_id, sender).into_inner()
+        }
+
+        /// Messages in the inbox or outbox of `who` sent between `from` and `to` inclusive,
+        /// oldest first. Bounded by `MaxInboxSize` and `MaxOutboxSize`.
+        pub fn messages_in_range(
+            who: T::AccountId,
+            from: BlockNumberFor<T>,
+            to: BlockNumberFor<T>,
+        ) -> Vec<T::Hash> {
+            if from > to {
+                return Vec::new();
+            }
+            
+            let mut found: Vec<(BlockNumberFor<T>, T::Hash)> = Vec::new();
+            let inbox = Inbox::<T>::get(&who);
+            let outbox = Outbox::<T>::get(&who);
+            for message_id in inbox.iter().chain(outbox.iter()) {
+                // Messages to oneself are in both boxes
+                if found.iter().any(|(_, id)| id == message_id) {
+                    continue;
+                }
+                let Some(message) = Messages::<T>::get(message_id) else {
+                    continue;
+                };
+                if message.timestamp >= from && message.timestamp <= to {
+                    found.push((message.timestamp, *message_id));
+                }
+            }
+            found.sort_by_key(|(timestamp, _)| *timestamp);
+            
+            found.into_iter().map(|(_, message_id)| message_id).collect()
+        }
+
+        /// Ids of `message_id` and the messages it was forwarded from, ending with the original.
+        /// Follows at most `MaxForwardDepth` links; when a link was deleted or the bound is hit
+        /// the original is still appended.
+        pub fn forward_chain(message_id: T::Hash) -> Vec<T::Hash> {
+            let Some(mut message) = Messages::<T>::get(message_id) else {
+                return Vec::new();
+            };
+            let original_id = message.original_id;
+            
+            let mut chain = vec![message_id];
+            for _ in 0..T::MaxForwardDepth::get() {
+                let Some(previous_id) = message.forwarded_from else {
+                    break;
+                };
+                chain.push(previous_id);
+                let Some(previous) = Messages::<T>::get(previous_id) else {
+                    break;
+                };
+                message = previous;
+            }
+            
+            if let Some(original_id) = original_id {
+                if chain.last() != Some(&original_id) {
+                    chain.push(original_id);
+                }
+            }
+            
+            chain
+        }
+
+        /// Up to `count` messages of the inbox of `who` starting at position `start`, with
+        /// their contents
+        pub fn inbox_page(who: T::AccountId, start: u32, count: u32) -> Vec<(T::Hash, MessageOf<T>)> {
+            Self::resolve_page(&Inbox::<T>::get(who), start, count)
+        }
+
+        /// Up to `count` messages of the outbox of `who` starting at position `start`, with
+        /// their contents
+        pub fn outbox_page(who: T::AccountId, start: u32, count: u32) -> Vec<(T::Hash, MessageOf<T>)> {
+            Self::resolve_page(&Outbox::<T>::get(who), start, count)
+        }
+
+        // Resolve a slice of message ids to the stored messages
+        fn resolve_page(message_ids: &[T::Hash], start: u32, count: u32) -> Vec<(T::Hash, MessageOf<T>)> {
+            message_ids
+                .iter()
+                .skip(start as usize)
+                .take(count as usize)
+                .filter_map(|message_id| Messages::<T>::get(message_id).map(|message| (*message_id, message)))
+                .collect()
+        }
+
+        /// Group a message was posted to, `None` for direct or unknown messages
+        pub fn message_group(message_id: T::Hash) -> Option<T::Hash> {
+            Messages::<T>::get(message_id).and_then(|message| message.group_id)
+        }
+
+        /// Number of current group members who have seen the message at `message_index`
+        pub fn group_message_seen_count(group_id: T::Hash, message_index: u32) -> u32 {
+            let Some(group) = Groups::<T>::get(group_id) else {
+                return 0;
+            };
+            
+            group
+                .members
+                .iter()
+                .filter(|member| {
+                    GroupReadCursor::<T>::get(group_id, *member)
+                        .is_some_and(|cursor| cursor >= message_index)
+                })
+                .count() as u32
         }
     }
     
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
-            // Could implement message expiration cleanup here
-            Weight::zero()
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            // Deliver the messages scheduled for this block
+            let due = ScheduledDeliveries::<T>::take(n);
+            for schedule_id in due.iter() {
+                if let Some(scheduled) = ScheduledMessages::<T>::take(schedule_id) {
+                    // The slot and deposit held since scheduling pass to the sent message
+                    Self::note_schedule_released(*schedule_id, &scheduled.sender);
+                    
+                    // A full inbox, outbox or message store, or a sender unable to cover the
+                    // deposit, drops the message without partial writes
+                    let _ = frame_support::storage::with_storage_layer(|| {
+                        Self::do_send_message(
+                            scheduled.sender,
+                            scheduled.recipient,
+                            vec![scheduled.content_cid.into_inner()],
+                            None,
+                        )
+                    });
+                }
+            }
+            
+            // Prune the messages expiring at this block
+            let expired = ExpiryIndex::<T>::take(n);
+            for message_id in expired.iter() {
+                Self::expire_message(*message_id);
+            }
+            
+            T::WeightInfo::on_initialize()
+                .saturating_add(
+                    T::WeightInfo::send_message()
+                        .saturating_add(T::WeightInfo::cancel_scheduled_message())
+                        .saturating_mul(due.len() as u64)
+                )
+                .saturating_add(T::WeightInfo::delete_message().saturating_mul(expired.len() as u64))
         }
     }
 }
\ No newline at end of file
@@ -11,12 +11,14 @@ use sp_runtime::{
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
     pub enum Test
     {
         System: frame_system,
+        Balances: pallet_balances,
         Messaging: pallet_messaging,
     }
 );
@@ -38,28 +40,96 @@ impl system::Config for Test {
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
     type SS58Prefix = ConstU16<42>;
     type OnSetCode = ();
-    type MaxConsumers = frame_support::traits::ConstU32<16>;
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type ExtensionsWeightInfo = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
 }
 
 parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeHoldReason = ();
+    type RuntimeFreezeReason = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type DoneSlashHandler = ();
+}
+
+parameter_types! {
+    pub static MessageDeposit: Balance = 0;
+    pub static GroupDeposit: Balance = 0;
     pub const MaxMessageLength: u32 = 1024;
     pub const MessageTTL: u64 = 100;
+    pub const MinMessageTTL: u64 = 10;
+    pub const MaxMessageTTL: u64 = 1_000;
+    pub const MaxScheduleDelay: u64 = 1_000;
+    pub static MaxGroupCidLength: u32 = 96;
+    pub static MaxInboxSize: u32 = 100;
+    pub const MaxOutboxSize: u32 = 100;
+    pub static MaxTotalMessages: u32 = 10_000;
+    pub const MaxGroupMembers: u32 = 50;
+    pub const MaxGroupsPerUser: u32 = 50;
+    pub const MaxGroupMessages: u32 = 1000;
+    pub const MaxReactionsPerMessage: u32 = 3;
+    pub const MaxAttachments: u32 = 3;
+    pub static MaxForwardDepth: u32 = 8;
 }
 
 impl pallet_messaging::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type MessageDeposit = MessageDeposit;
+    type GroupDeposit = GroupDeposit;
     type MaxMessageLength = MaxMessageLength;
+    type MaxGroupCidLength = MaxGroupCidLength;
+    type MaxAttachments = MaxAttachments;
+    type MaxInboxSize = MaxInboxSize;
+    type MaxOutboxSize = MaxOutboxSize;
+    type MaxTotalMessages = MaxTotalMessages;
+    type MaxGroupMembers = MaxGroupMembers;
+    type MaxGroupsPerUser = MaxGroupsPerUser;
+    type MaxGroupMessages = MaxGroupMessages;
+    type MaxReactionsPerMessage = MaxReactionsPerMessage;
+    type MaxForwardDepth = MaxForwardDepth;
     type MessageTTL = MessageTTL;
+    type MinMessageTTL = MinMessageTTL;
+    type MaxMessageTTL = MaxMessageTTL;
+    type MaxScheduleDelay = MaxScheduleDelay;
     type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+    let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1_000), (2, 1_000), (3, 1_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    
+    t.into()
 }
\ No newline at end of file
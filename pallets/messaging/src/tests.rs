@@ -1,6 +1,7 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
-use sp_runtime::traits::Hash;
+use crate::{mock::*, Error, Event, GroupActivity};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
 
 #[test]
 fn send_message_works() {
@@ -12,17 +13,17 @@ fn send_message_works() {
         let content_cid = vec![1, 2, 3, 4]; // IPFS CID
         
         // Send a message
-        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, content_cid.clone()));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, vec![content_cid.clone()]));
         
         // Check event
         System::assert_last_event(Event::MessageSent(
-            BlakeTwo256::hash_of(&(sender, recipient, 1)),
+            BlakeTwo256::hash_of(&(sender, recipient, 1u64)),
             sender,
             recipient,
         ).into());
         
         // Check storage
-        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1));
+        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1u64));
         assert!(Messaging::messages(message_id).is_some());
         assert!(Messaging::inbox(recipient).contains(&message_id));
         assert!(Messaging::outbox(sender).contains(&message_id));
@@ -39,14 +40,15 @@ fn read_message_works() {
         let content_cid = vec![1, 2, 3, 4]; // IPFS CID
         
         // Send a message
-        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, content_cid.clone()));
-        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, vec![content_cid.clone()]));
+        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1u64));
         
         // Read the message
         assert_ok!(Messaging::read_message(RuntimeOrigin::signed(recipient), message_id));
         
         // Check event
         System::assert_last_event(Event::MessageRead(message_id, recipient).into());
+        System::assert_has_event(Event::MessageReadReceipt(message_id, sender, recipient).into());
         
         // Check message is marked as read
         let message = Messaging::messages(message_id).unwrap();
@@ -54,6 +56,24 @@ fn read_message_works() {
     });
 }
 
+#[test]
+fn read_receipt_names_the_sender_once() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1, 2, 3]]));
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        
+        assert_ok!(Messaging::read_message(RuntimeOrigin::signed(2), message_id));
+        let receipt: RuntimeEvent = Event::MessageReadReceipt(message_id, 1, 2).into();
+        System::assert_has_event(receipt.clone());
+        
+        // Reading again does not issue another receipt
+        System::reset_events();
+        assert_ok!(Messaging::read_message(RuntimeOrigin::signed(2), message_id));
+        assert!(!System::events().iter().any(|record| record.event == receipt));
+    });
+}
+
 #[test]
 fn delete_message_works() {
     new_test_ext().execute_with(|| {
@@ -64,8 +84,8 @@ fn delete_message_works() {
         let content_cid = vec![1, 2, 3, 4]; // IPFS CID
         
         // Send a message
-        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, content_cid.clone()));
-        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, vec![content_cid.clone()]));
+        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1u64));
         
         // Delete the message as recipient
         assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(recipient), message_id));
@@ -79,6 +99,184 @@ fn delete_message_works() {
     });
 }
 
+#[test]
+fn message_deposit_is_reserved_and_refunded_on_delete() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MessageDeposit::set(100);
+        
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        assert_eq!(Balances::reserved_balance(1), 100);
+        assert_eq!(Balances::free_balance(1), 900);
+        assert_eq!(Messaging::message_deposits(message_id), Some(100));
+        
+        // The sender is refunded even when the recipient deletes the message
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(2), message_id));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
+        assert_eq!(Messaging::message_deposits(message_id), None);
+    });
+}
+
+#[test]
+fn messages_sent_in_the_same_block_keep_their_own_deposits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MessageDeposit::set(100);
+        
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![2]]));
+        
+        // The second message gets its own id instead of overwriting the first
+        let first_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        let second_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64, 1u32));
+        assert_eq!(Messaging::outbox(1).into_inner(), vec![first_id, second_id]);
+        assert_eq!(Messaging::messages(first_id).unwrap().content_cid[0].to_vec(), vec![1]);
+        assert_eq!(Messaging::messages(second_id).unwrap().content_cid[0].to_vec(), vec![2]);
+        assert_eq!(Balances::reserved_balance(1), 200);
+        
+        // Each deletion refunds exactly its own deposit
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(1), first_id));
+        assert_eq!(Balances::reserved_balance(1), 100);
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(1), second_id));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
+    });
+}
+
+#[test]
+fn message_ids_stop_after_a_bounded_number_of_salts() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        for cid in 0..8u8 {
+            assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![cid]]));
+        }
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![8]]),
+            Error::<Test>::DuplicateMessageId
+        );
+        
+        // The next block derives fresh ids
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![8]]));
+    });
+}
+
+#[test]
+fn message_deposit_is_refunded_on_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MessageDeposit::set(100);
+        
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        assert_eq!(Balances::reserved_balance(1), 100);
+        
+        Messaging::on_initialize(1 + MessageTTL::get());
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
+    });
+}
+
+#[test]
+fn message_requires_balance_for_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MessageDeposit::set(1_001);
+        
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]),
+            Error::<Test>::InsufficientBalance
+        );
+        
+        // Accounts without funds cannot post to groups either
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), b"group".to_vec(), vec![4]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, b"group".to_vec(), 1u64));
+        MessageDeposit::set(1);
+        assert_noop!(
+            Messaging::send_group_message(RuntimeOrigin::signed(4), group_id, vec![vec![1]]),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn full_message_store_rejects_sends_until_space_is_freed() {
+    new_test_ext().execute_with(|| {
+        MaxTotalMessages::set(3);
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), b"group".to_vec(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, b"group".to_vec(), 0u64));
+        
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(2), 1, vec![vec![2]]));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![3]]));
+        assert_eq!(Messaging::total_message_count(), 3);
+        
+        // Direct and group messages share the store
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(3), 1, vec![vec![4]]),
+            Error::<Test>::MessageStorageFull
+        );
+        assert_noop!(
+            Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![4]]),
+            Error::<Test>::MessageStorageFull
+        );
+        
+        // Deleting a message frees its slot
+        let first = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(1), first));
+        assert_eq!(Messaging::total_message_count(), 2);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(3), 1, vec![vec![4]]));
+        assert_eq!(Messaging::total_message_count(), 3);
+    });
+}
+
+#[test]
+fn total_message_count_follows_expiry_and_group_deletion() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), b"group".to_vec(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, b"group".to_vec(), 1u64));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1]]));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![2]]));
+        
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![3]]));
+        assert_eq!(Messaging::total_message_count(), 3);
+        
+        assert_ok!(Messaging::delete_group(RuntimeOrigin::signed(1), group_id));
+        assert_eq!(Messaging::total_message_count(), 1);
+        
+        Messaging::on_initialize(2 + MessageTTL::get());
+        assert_eq!(Messaging::total_message_count(), 0);
+    });
+}
+
+#[test]
+fn total_message_count_counts_each_message_sent_in_one_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), b"group".to_vec(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, b"group".to_vec(), 1u64));
+        
+        for cid in 1..=3u8 {
+            assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![cid]]));
+            assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![cid]]));
+        }
+        assert_eq!(Messaging::total_message_count(), 6);
+        
+        // Every removal is matched by exactly one earlier increment
+        for message_id in Messaging::inbox(2) {
+            assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(2), message_id));
+        }
+        assert_eq!(Messaging::total_message_count(), 3);
+        assert_ok!(Messaging::delete_group(RuntimeOrigin::signed(1), group_id));
+        assert_eq!(Messaging::total_message_count(), 0);
+    });
+}
+
 #[test]
 fn invalid_cid() {
     new_test_ext().execute_with(|| {
@@ -90,7 +288,7 @@ fn invalid_cid() {
         
         // Attempt to send a message with invalid CID
         assert_noop!(
-            Messaging::send_message(RuntimeOrigin::signed(sender), recipient, content_cid),
+            Messaging::send_message(RuntimeOrigin::signed(sender), recipient, vec![content_cid]),
             Error::<Test>::InvalidCID
         );
     });
@@ -107,8 +305,8 @@ fn unauthorized_read() {
         let content_cid = vec![1, 2, 3, 4]; // IPFS CID
         
         // Send a message
-        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, content_cid.clone()));
-        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(sender), recipient, vec![content_cid.clone()]));
+        let message_id = BlakeTwo256::hash_of(&(sender, recipient, 1u64));
         
         // Attempt to read by unauthorized user
         assert_noop!(
@@ -138,7 +336,7 @@ fn create_group_works() {
         ));
         
         // Check group exists
-        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
         let group = Messaging::groups(group_id).unwrap();
         
         assert_eq!(group.owner, owner);
@@ -171,7 +369,7 @@ fn add_member_works() {
             vec![member1]
         ));
         
-        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
         
         // Add a new member
         assert_ok!(Messaging::add_member(
@@ -204,7 +402,7 @@ fn remove_member_works() {
             vec![member1, member2]
         ));
         
-        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
         
         // Remove a member
         assert_ok!(Messaging::remove_member(
@@ -220,6 +418,107 @@ fn remove_member_works() {
     });
 }
 
+#[test]
+fn remove_members_skips_owner_and_non_members() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2, 3, 4]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_noop!(
+            Messaging::remove_members(RuntimeOrigin::signed(2), group_id, vec![3].try_into().unwrap()),
+            Error::<Test>::NotGroupAdmin
+        );
+        
+        assert_ok!(Messaging::remove_members(
+            RuntimeOrigin::signed(1),
+            group_id,
+            vec![2, 5, 1, 4].try_into().unwrap()
+        ));
+        System::assert_has_event(Event::MemberRemoved(group_id, 2).into());
+        System::assert_has_event(Event::MemberRemoved(group_id, 4).into());
+        
+        let mut members = Messaging::groups(group_id).unwrap().members.into_inner();
+        members.sort();
+        assert_eq!(members, vec![1, 3]);
+        assert!(!Messaging::group_membership(2).contains(&group_id));
+        assert!(!Messaging::group_membership(4).contains(&group_id));
+        assert!(Messaging::group_membership(1).contains(&group_id));
+    });
+}
+
+#[test]
+fn admin_can_manage_members() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2, 3]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        // Only the owner appoints admins, and only among members
+        assert_noop!(
+            Messaging::add_admin(RuntimeOrigin::signed(2), group_id, 2),
+            Error::<Test>::NotGroupOwner
+        );
+        assert_noop!(
+            Messaging::add_admin(RuntimeOrigin::signed(1), group_id, 4),
+            Error::<Test>::NotGroupMember
+        );
+        assert_ok!(Messaging::add_admin(RuntimeOrigin::signed(1), group_id, 2));
+        System::assert_last_event(Event::AdminAdded(group_id, 2).into());
+        assert_noop!(
+            Messaging::add_admin(RuntimeOrigin::signed(1), group_id, 2),
+            Error::<Test>::AlreadyAdmin
+        );
+        
+        assert_ok!(Messaging::add_member(RuntimeOrigin::signed(2), group_id, 4));
+        assert_ok!(Messaging::remove_member(RuntimeOrigin::signed(2), group_id, 3));
+        let group = Messaging::groups(group_id).unwrap();
+        assert!(group.members.contains(&4));
+        assert!(!group.members.contains(&3));
+        
+        // Admins cannot remove the owner
+        assert_noop!(
+            Messaging::remove_member(RuntimeOrigin::signed(2), group_id, 1),
+            Error::<Test>::NotAuthorized
+        );
+        
+        // A demoted admin is a regular member again
+        assert_ok!(Messaging::remove_admin(RuntimeOrigin::signed(1), group_id, 2));
+        System::assert_last_event(Event::AdminRemoved(group_id, 2).into());
+        assert_noop!(
+            Messaging::add_member(RuntimeOrigin::signed(2), group_id, 5),
+            Error::<Test>::NotGroupAdmin
+        );
+    });
+}
+
+#[test]
+fn regular_member_cannot_manage_members() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2, 3]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_noop!(
+            Messaging::add_member(RuntimeOrigin::signed(2), group_id, 4),
+            Error::<Test>::NotGroupAdmin
+        );
+        assert_noop!(
+            Messaging::remove_member(RuntimeOrigin::signed(2), group_id, 3),
+            Error::<Test>::NotGroupAdmin
+        );
+        
+        // The owner cannot be demoted
+        assert_noop!(
+            Messaging::remove_admin(RuntimeOrigin::signed(1), group_id, 1),
+            Error::<Test>::NotGroupAdmin
+        );
+    });
+}
+
 #[test]
 fn send_group_message_works() {
     new_test_ext().execute_with(|| {
@@ -237,17 +536,17 @@ fn send_group_message_works() {
             vec![member]
         ));
         
-        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
         
         // Send a group message
         assert_ok!(Messaging::send_group_message(
             RuntimeOrigin::signed(member),
             group_id,
-            content_cid.clone()
+            vec![content_cid.clone()]
         ));
         
         // Check message was stored
-        let message_id = BlakeTwo256::hash_of(&(member, group_id, 1));
+        let message_id = BlakeTwo256::hash_of(&(member, group_id, 1u64));
         assert!(Messaging::messages(message_id).is_some());
         assert!(Messaging::group_messages(group_id).contains(&message_id));
         assert!(Messaging::outbox(member).contains(&message_id));
@@ -255,33 +554,1085 @@ fn send_group_message_works() {
 }
 
 #[test]
-fn non_member_cannot_send_group_message() {
+fn send_reply_links_to_parent() {
     new_test_ext().execute_with(|| {
         System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1, 2, 3]]));
+        let parent_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
         
-        let owner = 1;
-        let member = 2;
-        let non_member = 3;
-        let name = b"test group".to_vec();
-        let content_cid = vec![1, 2, 3, 4]; // IPFS CID
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_reply(RuntimeOrigin::signed(2), 1, vec![vec![4, 5, 6]], parent_id));
+        let reply_id = BlakeTwo256::hash_of(&(2u64, 1u64, 2u64));
+        System::assert_has_event(Event::MessageSent(reply_id, 2, 1).into());
+        System::assert_last_event(Event::ReplySent(reply_id, parent_id).into());
+        assert_eq!(Messaging::messages(reply_id).unwrap().reply_to, Some(parent_id));
+        assert!(Messaging::inbox(1).contains(&reply_id));
+    });
+}
+
+#[test]
+fn send_reply_requires_known_parent_and_participant() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            Messaging::send_reply(RuntimeOrigin::signed(2), 1, vec![vec![1, 2, 3]], H256::repeat_byte(7)),
+            Error::<Test>::MessageNotFound
+        );
         
-        // Create a group
-        assert_ok!(Messaging::create_group(
-            RuntimeOrigin::signed(owner),
-            name.clone(),
-            vec![member]
-        ));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1, 2, 3]]));
+        let parent_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        assert_noop!(
+            Messaging::send_reply(RuntimeOrigin::signed(3), 1, vec![vec![4, 5, 6]], parent_id),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn forwarded_messages_resolve_to_the_original() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1, 2, 3]]));
+        let original = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        
+        // Only participants can forward
+        assert_noop!(
+            Messaging::forward_message(RuntimeOrigin::signed(3), original, 4),
+            Error::<Test>::NotAuthorized
+        );
         
-        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1));
+        assert_ok!(Messaging::forward_message(RuntimeOrigin::signed(2), original, 3));
+        let first = BlakeTwo256::hash_of(&(2u64, 3u64, 1u64));
+        System::assert_last_event(Event::MessageForwarded(first, original, original).into());
         
-        // Attempt to send a group message as non-member
+        assert_ok!(Messaging::forward_message(RuntimeOrigin::signed(3), first, 4));
+        let second = BlakeTwo256::hash_of(&(3u64, 4u64, 1u64));
+        System::assert_last_event(Event::MessageForwarded(second, first, original).into());
+        
+        let forwarded = Messaging::messages(second).unwrap();
+        assert_eq!(forwarded.content_cid[0].to_vec(), vec![1, 2, 3]);
+        assert_eq!(forwarded.forwarded_from, Some(first));
+        assert_eq!(forwarded.original_id, Some(original));
+        
+        assert_eq!(Messaging::forward_chain(second), vec![second, first, original]);
+        assert_eq!(Messaging::forward_chain(original), vec![original]);
+        
+        // The walk is bounded but still ends with the original
+        MaxForwardDepth::set(0);
+        assert_eq!(Messaging::forward_chain(second), vec![second, original]);
+    });
+}
+
+#[test]
+fn sender_can_forward_a_message_to_a_new_recipient() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1], vec![2]]));
+        let original = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        
+        assert_ok!(Messaging::forward_message(RuntimeOrigin::signed(1), original, 3));
+        let forwarded = BlakeTwo256::hash_of(&(1u64, 3u64, 1u64));
+        System::assert_has_event(Event::MessageSent(forwarded, 1, 3).into());
+        System::assert_last_event(Event::MessageForwarded(forwarded, original, original).into());
+        
+        // The new message reuses every content CID without re-uploading
+        let message = Messaging::messages(forwarded).unwrap();
+        assert_eq!(message.recipient, 3);
+        assert_eq!(message.content_cid, Messaging::messages(original).unwrap().content_cid);
+        assert!(Messaging::inbox(3).contains(&forwarded));
+    });
+}
+
+#[test]
+fn only_its_author_forwards_a_group_message_with_its_long_cids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), b"group".to_vec(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, b"group".to_vec(), 1u64));
+        
+        // A CID over the 64 byte direct limit but within the group limit
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1; 96]]));
+        let group_message = BlakeTwo256::hash_of(&(1u64, group_id, 1u64));
+        
+        // Other members can read it but not forward it
         assert_noop!(
-            Messaging::send_group_message(
-                RuntimeOrigin::signed(non_member),
-                group_id,
-                content_cid.clone()
-            ),
-            Error::<Test>::NotGroupMember
+            Messaging::forward_message(RuntimeOrigin::signed(2), group_message, 3),
+            Error::<Test>::NotAuthorized
+        );
+        
+        assert_ok!(Messaging::forward_message(RuntimeOrigin::signed(1), group_message, 3));
+        let forwarded = BlakeTwo256::hash_of(&(1u64, 3u64, 1u64));
+        let message = Messaging::messages(forwarded).unwrap();
+        assert_eq!(message.content_cid[0].to_vec(), vec![1; 96]);
+        assert_eq!(message.group_id, None);
+        assert!(Messaging::inbox(3).contains(&forwarded));
+    });
+}
+
+#[test]
+fn inbox_and_outbox_pages_resolve_messages() {
+    new_test_ext().execute_with(|| {
+        let mut sent = Vec::new();
+        for block in 1..=5u64 {
+            System::set_block_number(block);
+            assert_ok!(Messaging::send_message(RuntimeOrigin::signed(2), 1, vec![vec![block as u8]]));
+            sent.push(BlakeTwo256::hash_of(&(2u64, 1u64, block)));
+        }
+        
+        let page = Messaging::inbox_page(1, 1, 2);
+        assert_eq!(page.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![sent[1], sent[2]]);
+        assert_eq!(page[0].1.content_cid[0].to_vec(), vec![2]);
+        assert_eq!(page[1].1.sender, 2);
+        assert_eq!(page[1].1.timestamp, 3);
+        
+        // Pages are cut at the end of the box
+        assert_eq!(Messaging::outbox_page(2, 3, 10).len(), 2);
+        assert!(Messaging::inbox_page(1, 5, 10).is_empty());
+        assert!(Messaging::outbox_page(1, 0, 10).is_empty());
+    });
+}
+
+#[test]
+fn messages_in_range_filters_by_timestamp() {
+    new_test_ext().execute_with(|| {
+        let mut sent = Vec::new();
+        for block in 1..=4u64 {
+            System::set_block_number(block);
+            assert_ok!(Messaging::send_message(RuntimeOrigin::signed(2), 1, vec![vec![1, 2, 3]]));
+            sent.push(BlakeTwo256::hash_of(&(2u64, 1u64, block)));
+        }
+        // Outgoing messages are found too
+        System::set_block_number(3);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 3, vec![vec![4, 5, 6]]));
+        let outgoing = BlakeTwo256::hash_of(&(1u64, 3u64, 3u64));
+        
+        assert_eq!(Messaging::messages_in_range(1, 2, 3), vec![sent[1], sent[2], outgoing]);
+        assert_eq!(Messaging::messages_in_range(1, 4, 10), vec![sent[3]]);
+        assert!(Messaging::messages_in_range(1, 5, 10).is_empty());
+        
+        // An empty range matches nothing
+        assert!(Messaging::messages_in_range(1, 3, 2).is_empty());
+    });
+}
+
+#[test]
+fn blocked_sender_cannot_deliver_until_unblocked() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        assert_ok!(Messaging::block_user(RuntimeOrigin::signed(2), 1));
+        assert_eq!(Messaging::blocked_senders(2, 1), Some(()));
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1, 2, 3]]),
+            Error::<Test>::SenderBlocked
+        );
+        
+        // Other senders are unaffected
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(3), 2, vec![vec![1, 2, 3]]));
+        
+        assert_ok!(Messaging::unblock_user(RuntimeOrigin::signed(2), 1));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1, 2, 3]]));
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        assert!(Messaging::inbox(2).contains(&message_id));
+    });
+}
+
+#[test]
+fn delete_group_clears_all_group_storage() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2, 3]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1]]));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![2]]));
+        let first = BlakeTwo256::hash_of(&(1u64, group_id, 1u64));
+        let second = BlakeTwo256::hash_of(&(2u64, group_id, 1u64));
+        assert_ok!(Messaging::react(RuntimeOrigin::signed(3), first, 1));
+        assert_ok!(Messaging::update_group_read_cursor(RuntimeOrigin::signed(3), group_id, 1));
+        
+        // Only the owner may delete
+        assert_noop!(
+            Messaging::delete_group(RuntimeOrigin::signed(2), group_id),
+            Error::<Test>::NotGroupOwner
         );
+        
+        assert_ok!(Messaging::delete_group(RuntimeOrigin::signed(1), group_id));
+        System::assert_last_event(Event::GroupDeleted(group_id).into());
+        
+        assert!(Messaging::groups(group_id).is_none());
+        for member in [1, 2, 3] {
+            assert!(!Messaging::group_membership(member).contains(&group_id));
+            assert!(Messaging::group_sender_messages(group_id, member).is_empty());
+            assert_eq!(Messaging::group_read_cursor(group_id, member), None);
+        }
+        assert!(Messaging::group_messages(group_id).is_empty());
+        assert_eq!(Messaging::group_stats(group_id), GroupActivity::default());
+        for message_id in [first, second] {
+            assert!(Messaging::messages(message_id).is_none());
+            assert!(Messaging::reactions(message_id).is_empty());
+        }
+        assert!(Messaging::outbox(1).is_empty());
+        assert!(Messaging::outbox(2).is_empty());
+        assert!(Messaging::expiry_index(1 + MessageTTL::get()).is_empty());
+    });
+}
+
+#[test]
+fn group_deposit_is_refunded_to_the_creator_on_delete() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        GroupDeposit::set(300);
+        MessageDeposit::set(10);
+        
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        assert_eq!(Balances::reserved_balance(1), 300);
+        assert_eq!(Messaging::group_deposits(group_id), Some((1, 300)));
+        
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![1]]));
+        assert_eq!(Balances::reserved_balance(2), 10);
+        
+        // The deposit stays with the creator after the group changes hands
+        assert_ok!(Messaging::transfer_group_ownership(RuntimeOrigin::signed(1), group_id, 2));
+        assert_ok!(Messaging::delete_group(RuntimeOrigin::signed(2), group_id));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 1_000);
+        assert_eq!(Messaging::group_deposits(group_id), None);
+    });
+}
+
+#[test]
+fn groups_created_in_the_same_block_keep_their_own_deposits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        GroupDeposit::set(300);
+        
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![3]));
+        
+        // The second group gets its own id instead of overwriting the first
+        let first_id = BlakeTwo256::hash_of(&(1u64, name.clone(), 1u64));
+        let second_id = BlakeTwo256::hash_of(&(1u64, name, 1u64, 1u32));
+        assert_eq!(Messaging::groups(first_id).unwrap().members.into_inner(), vec![1, 2]);
+        assert_eq!(Messaging::groups(second_id).unwrap().members.into_inner(), vec![1, 3]);
+        assert_eq!(Messaging::group_membership(1).into_inner(), vec![first_id, second_id]);
+        assert_eq!(Balances::reserved_balance(1), 600);
+        
+        // Each deletion refunds exactly its own deposit
+        assert_ok!(Messaging::delete_group(RuntimeOrigin::signed(1), first_id));
+        assert_eq!(Balances::reserved_balance(1), 300);
+        assert_ok!(Messaging::delete_group(RuntimeOrigin::signed(1), second_id));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn group_requires_balance_for_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        GroupDeposit::set(1_001);
+        
+        assert_noop!(
+            Messaging::create_group(RuntimeOrigin::signed(1), b"group".to_vec(), vec![2]),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn message_carries_several_attachments() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let attachments = vec![vec![1, 2, 3], vec![4; 64], vec![5]];
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, attachments.clone()));
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        let stored: Vec<Vec<u8>> = Messaging::messages(message_id)
+            .unwrap()
+            .content_cid
+            .into_iter()
+            .map(|cid| cid.into_inner())
+            .collect();
+        assert_eq!(stored, attachments);
+        
+        // One attachment over the cap
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 3, vec![vec![1]; 4]),
+            Error::<Test>::TooManyAttachments
+        );
+        
+        // Every CID is checked, and a message needs at least one
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 3, vec![vec![1], vec![2; 65]]),
+            Error::<Test>::InvalidCID
+        );
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 3, Vec::new()),
+            Error::<Test>::InvalidCID
+        );
+    });
+}
+
+#[test]
+fn group_and_direct_cid_limits_are_separate() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        // Group messages take up to MaxGroupCidLength (96) bytes
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1; 96]]));
+        assert_noop!(
+            Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1; 97]]),
+            Error::<Test>::InvalidCID
+        );
+        
+        // Direct messages keep their own 64 byte limit
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1; 64]]));
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1; 65]]),
+            Error::<Test>::InvalidCID
+        );
+        
+        // A group limit below the direct one only restricts group messages
+        MaxGroupCidLength::set(32);
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1; 32]]));
+        assert_noop!(
+            Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1; 33]]),
+            Error::<Test>::InvalidCID
+        );
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1; 64]]));
+    });
+}
+
+#[test]
+fn full_group_log_is_reported() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        // Fill the group's message log to its bound
+        crate::GroupMessages::<Test>::mutate(group_id, |messages| {
+            for i in 0..MaxGroupMessages::get() as u64 {
+                messages.try_push(H256::from_low_u64_be(i)).unwrap();
+            }
+        });
+        
+        assert_noop!(
+            Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![1, 2, 3]]),
+            Error::<Test>::GroupMessageLogFull
+        );
+    });
+}
+
+#[test]
+fn member_can_leave_group() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2, 3]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_ok!(Messaging::leave_group(RuntimeOrigin::signed(2), group_id));
+        System::assert_last_event(Event::MemberRemoved(group_id, 2).into());
+        assert!(!Messaging::groups(group_id).unwrap().members.contains(&2));
+        assert!(!Messaging::group_membership(2).contains(&group_id));
+        
+        // Leaving twice, or a group never joined, fails
+        assert_noop!(
+            Messaging::leave_group(RuntimeOrigin::signed(2), group_id),
+            Error::<Test>::NotGroupMember
+        );
+        assert_noop!(
+            Messaging::leave_group(RuntimeOrigin::signed(4), group_id),
+            Error::<Test>::NotGroupMember
+        );
+    });
+}
+
+#[test]
+fn owner_cannot_leave_group() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_noop!(
+            Messaging::leave_group(RuntimeOrigin::signed(1), group_id),
+            Error::<Test>::NotAuthorized
+        );
+        assert!(Messaging::groups(group_id).unwrap().members.contains(&1));
+    });
+}
+
+#[test]
+fn owner_can_transfer_group_ownership() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_ok!(Messaging::transfer_group_ownership(RuntimeOrigin::signed(1), group_id, 2));
+        System::assert_last_event(Event::GroupOwnershipTransferred(group_id, 1, 2).into());
+        assert_eq!(Messaging::groups(group_id).unwrap().owner, 2);
+        
+        // The former owner is now a regular member and may leave
+        assert_ok!(Messaging::leave_group(RuntimeOrigin::signed(1), group_id));
+    });
+}
+
+#[test]
+fn group_ownership_transfer_is_restricted() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_noop!(
+            Messaging::transfer_group_ownership(RuntimeOrigin::signed(2), group_id, 2),
+            Error::<Test>::NotGroupOwner
+        );
+        assert_noop!(
+            Messaging::transfer_group_ownership(RuntimeOrigin::signed(1), group_id, 3),
+            Error::<Test>::NotGroupMember
+        );
+    });
+}
+
+#[test]
+fn non_member_cannot_send_group_message() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let member = 2;
+        let non_member = 3;
+        let name = b"test group".to_vec();
+        let content_cid = vec![1, 2, 3, 4]; // IPFS CID
+        
+        // Create a group
+        assert_ok!(Messaging::create_group(
+            RuntimeOrigin::signed(owner),
+            name.clone(),
+            vec![member]
+        ));
+        
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        
+        // Attempt to send a group message as non-member
+        assert_noop!(
+            Messaging::send_group_message(
+                RuntimeOrigin::signed(non_member),
+                group_id,
+                vec![content_cid.clone()]
+            ),
+            Error::<Test>::NotGroupMember
+        );
+    });
+}
+#[test]
+fn group_message_seen_count_follows_read_cursors() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(
+            RuntimeOrigin::signed(owner),
+            name.clone(),
+            vec![2, 3, 4]
+        ));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        
+        // Two messages in the group log: index 0 and index 1
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![1, 2, 3]]));
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(owner), group_id, vec![vec![4, 5, 6]]));
+        
+        assert_eq!(Messaging::group_message_seen_count(group_id, 0), 0);
+        
+        // Member 3 catches up on both messages, member 4 only on the first
+        assert_ok!(Messaging::update_group_read_cursor(RuntimeOrigin::signed(3), group_id, 1));
+        assert_ok!(Messaging::update_group_read_cursor(RuntimeOrigin::signed(4), group_id, 0));
+        System::assert_last_event(Event::GroupReadCursorUpdated(group_id, 4, 0).into());
+        
+        assert_eq!(Messaging::group_message_seen_count(group_id, 0), 2);
+        assert_eq!(Messaging::group_message_seen_count(group_id, 1), 1);
+        
+        // Cursors never move backwards
+        assert_ok!(Messaging::update_group_read_cursor(RuntimeOrigin::signed(3), group_id, 0));
+        assert_eq!(Messaging::group_read_cursor(group_id, 3), Some(1));
+        
+        // Past the end of the log is rejected
+        assert_noop!(
+            Messaging::update_group_read_cursor(RuntimeOrigin::signed(4), group_id, 2),
+            Error::<Test>::InvalidMessageIndex
+        );
+    });
+}
+
+#[test]
+fn group_messages_are_flagged_and_cleaned_up_on_delete() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let member = 2;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(owner), name.clone(), vec![member]));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        
+        // One direct and one group message
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(member), owner, vec![vec![1, 2, 3]]));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(member), group_id, vec![vec![4, 5, 6]]));
+        let direct_id = BlakeTwo256::hash_of(&(member, owner, 1u64));
+        let group_message_id = BlakeTwo256::hash_of(&(member, group_id, 1u64));
+        
+        assert!(!Messaging::is_group_message(direct_id));
+        assert!(Messaging::is_group_message(group_message_id));
+        assert_eq!(Messaging::messages(group_message_id).unwrap().group_id, Some(group_id));
+        
+        // Deleting the group message also drops it from the group log
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(member), group_message_id));
+        assert!(Messaging::messages(group_message_id).is_none());
+        assert!(!Messaging::group_messages(group_id).contains(&group_message_id));
+        assert!(!Messaging::outbox(member).contains(&group_message_id));
+    });
+}
+
+#[test]
+fn deleting_group_message_keeps_read_cursors_aligned() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(owner), name.clone(), vec![2, 3]));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![1]]));
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(owner), group_id, vec![vec![2]]));
+        let first = BlakeTwo256::hash_of(&(2u64, group_id, 1u64));
+        let second = BlakeTwo256::hash_of(&(owner, group_id, 2u64));
+        
+        // Member 2 saw the first message only, member 3 saw both
+        assert_ok!(Messaging::update_group_read_cursor(RuntimeOrigin::signed(2), group_id, 0));
+        assert_ok!(Messaging::update_group_read_cursor(RuntimeOrigin::signed(3), group_id, 1));
+        
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(2), first));
+        
+        // The dangling id is gone and cursors still refer to the same messages
+        assert_eq!(Messaging::group_messages(group_id).into_inner(), vec![second]);
+        assert_eq!(Messaging::group_read_cursor(group_id, 2), None);
+        assert_eq!(Messaging::group_read_cursor(group_id, 3), Some(0));
+        assert_eq!(Messaging::group_message_seen_count(group_id, 0), 1);
+    });
+}
+
+#[test]
+fn group_thread_follows_reply_links() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(owner), name.clone(), vec![2, 3]));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        
+        // root <- a <- c, root <- b, plus an unrelated top-level message
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(owner), group_id, vec![vec![1]]));
+        let root = BlakeTwo256::hash_of(&(owner, group_id, 1u64));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![2]]));
+        let unrelated = BlakeTwo256::hash_of(&(2u64, group_id, 1u64));
+        
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_group_reply(RuntimeOrigin::signed(2), group_id, vec![vec![3]], root));
+        let a = BlakeTwo256::hash_of(&(2u64, group_id, 2u64));
+        System::assert_last_event(Event::ReplySent(a, root).into());
+        assert_ok!(Messaging::send_group_reply(RuntimeOrigin::signed(3), group_id, vec![vec![4]], root));
+        let b = BlakeTwo256::hash_of(&(3u64, group_id, 2u64));
+        
+        System::set_block_number(3);
+        assert_ok!(Messaging::send_group_reply(RuntimeOrigin::signed(owner), group_id, vec![vec![5]], a));
+        let c = BlakeTwo256::hash_of(&(owner, group_id, 3u64));
+        
+        assert_eq!(
+            Messaging::group_thread(group_id, root),
+            vec![(root, None), (a, Some(root)), (b, Some(root)), (c, Some(a))]
+        );
+        assert_eq!(Messaging::group_thread(group_id, a), vec![(a, None), (c, Some(a))]);
+        assert_eq!(Messaging::group_thread(group_id, unrelated), vec![(unrelated, None)]);
+    });
+}
+
+#[test]
+fn group_reply_requires_parent_in_same_group() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(owner), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(owner), 2, vec![vec![1]]));
+        let direct = BlakeTwo256::hash_of(&(owner, 2u64, 1u64));
+        
+        assert_noop!(
+            Messaging::send_group_reply(RuntimeOrigin::signed(2), group_id, vec![vec![2]], direct),
+            Error::<Test>::ParentNotInGroup
+        );
+        assert_noop!(
+            Messaging::send_group_reply(RuntimeOrigin::signed(2), group_id, vec![vec![2]], H256::zero()),
+            Error::<Test>::MessageNotFound
+        );
+    });
+}
+
+#[test]
+fn group_messages_are_indexed_by_sender() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(owner), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(owner), group_id, vec![vec![1]]));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![2]]));
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(owner), group_id, vec![vec![3]]));
+        
+        let owner_first = BlakeTwo256::hash_of(&(owner, group_id, 1u64));
+        let owner_second = BlakeTwo256::hash_of(&(owner, group_id, 2u64));
+        let member_message = BlakeTwo256::hash_of(&(2u64, group_id, 1u64));
+        
+        // Each sender's index holds only their own messages
+        assert_eq!(Messaging::group_messages_by_sender(group_id, owner), vec![owner_first, owner_second]);
+        assert_eq!(Messaging::group_messages_by_sender(group_id, 2), vec![member_message]);
+        assert!(Messaging::group_messages_by_sender(group_id, 3).is_empty());
+        
+        // Deleting a message drops it from the index
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(owner), owner_first));
+        assert_eq!(Messaging::group_messages_by_sender(group_id, owner), vec![owner_second]);
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(2), member_message));
+        assert!(!crate::GroupSenderMessages::<Test>::contains_key(group_id, 2));
+    });
+}
+
+#[test]
+fn inbox_is_bounded_by_max_inbox_size() {
+    new_test_ext().execute_with(|| {
+        MaxInboxSize::set(2);
+        for block in 1..=2u64 {
+            System::set_block_number(block);
+            assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        }
+        
+        System::set_block_number(3);
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]),
+            Error::<Test>::InboxFull
+        );
+        
+        // Other inboxes are unaffected
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 3, vec![vec![1]]));
+    });
+}
+
+#[test]
+fn message_group_distinguishes_group_and_direct_messages() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        let direct = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![2]]));
+        let group_message = BlakeTwo256::hash_of(&(1u64, group_id, 1u64));
+        
+        assert_eq!(Messaging::message_group(group_message), Some(group_id));
+        assert_eq!(Messaging::message_group(direct), None);
+        assert_eq!(Messaging::message_group(H256::repeat_byte(9)), None);
+    });
+}
+
+#[test]
+fn reactions_are_capped_per_message() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = b"test group".to_vec();
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(1), name.clone(), vec![2, 3, 4]));
+        let group_id = BlakeTwo256::hash_of(&(1u64, name, 1u64));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(1), group_id, vec![vec![1]]));
+        let message_id = BlakeTwo256::hash_of(&(1u64, group_id, 1u64));
+        
+        // Non-members cannot react
+        assert_noop!(
+            Messaging::react(RuntimeOrigin::signed(5), message_id, 1),
+            Error::<Test>::NotAuthorized
+        );
+        
+        // Up to MaxReactionsPerMessage (3) reactors
+        for reactor in 1..=3u64 {
+            assert_ok!(Messaging::react(RuntimeOrigin::signed(reactor), message_id, 1));
+        }
+        System::assert_last_event(Event::ReactionAdded(message_id, 3, 1).into());
+        assert_noop!(
+            Messaging::react(RuntimeOrigin::signed(4), message_id, 1),
+            Error::<Test>::TooManyReactions
+        );
+        
+        // Existing reactors can still change and remove theirs
+        assert_ok!(Messaging::react(RuntimeOrigin::signed(2), message_id, 7));
+        assert_eq!(Messaging::reactions(message_id).into_inner(), vec![(1, 1), (2, 7), (3, 1)]);
+        assert_ok!(Messaging::react(RuntimeOrigin::signed(2), message_id, 7));
+        System::assert_last_event(Event::ReactionRemoved(message_id, 2).into());
+        
+        // Which frees a slot
+        assert_ok!(Messaging::react(RuntimeOrigin::signed(4), message_id, 1));
+        assert_eq!(Messaging::reactions(message_id).len(), 3);
+        
+        // Reactions go with the message
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(1), message_id));
+        assert!(!crate::Reactions::<Test>::contains_key(message_id));
+    });
+}
+
+#[test]
+fn group_stats_track_message_count_and_activity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(owner), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        assert_eq!(Messaging::group_stats(group_id), GroupActivity::default());
+        
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(owner), group_id, vec![vec![1]]));
+        System::set_block_number(5);
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(2), group_id, vec![vec![2]]));
+        
+        assert_eq!(
+            Messaging::group_stats(group_id),
+            GroupActivity { message_count: 2, last_activity: 5 }
+        );
+        
+        // Deleting a message lowers the count but keeps the last activity
+        let first = BlakeTwo256::hash_of(&(owner, group_id, 1u64));
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(owner), first));
+        assert_eq!(
+            Messaging::group_stats(group_id),
+            GroupActivity { message_count: 1, last_activity: 5 }
+        );
+    });
+}
+
+#[test]
+fn scheduled_message_is_delivered_at_its_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        assert_ok!(Messaging::schedule_message(RuntimeOrigin::signed(1), 2, vec![1, 2, 3], 5));
+        let schedule_id = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64, 1u64));
+        System::assert_last_event(Event::MessageScheduled(schedule_id, 1, 2, 5).into());
+        
+        Messaging::on_initialize(4);
+        assert!(Messaging::inbox(2).is_empty());
+        
+        System::set_block_number(5);
+        Messaging::on_initialize(5);
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64));
+        assert_eq!(Messaging::inbox(2).to_vec(), vec![message_id]);
+        assert!(Messaging::scheduled_messages(schedule_id).is_none());
+        
+        // Once delivered it can no longer be cancelled
+        assert_noop!(
+            Messaging::cancel_scheduled_message(RuntimeOrigin::signed(1), schedule_id),
+            Error::<Test>::ScheduledMessageNotFound
+        );
+    });
+}
+
+#[test]
+fn cancelled_scheduled_message_is_never_delivered() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        assert_ok!(Messaging::schedule_message(RuntimeOrigin::signed(1), 2, vec![1, 2, 3], 5));
+        let schedule_id = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64, 1u64));
+        
+        // Only the scheduler may cancel
+        assert_noop!(
+            Messaging::cancel_scheduled_message(RuntimeOrigin::signed(2), schedule_id),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Messaging::cancel_scheduled_message(RuntimeOrigin::signed(1), schedule_id));
+        System::assert_last_event(Event::ScheduledMessageCancelled(schedule_id).into());
+        assert!(Messaging::scheduled_deliveries(5).is_empty());
+        
+        System::set_block_number(5);
+        Messaging::on_initialize(5);
+        assert!(Messaging::inbox(2).is_empty());
+        
+        // Delivery must be in the future
+        assert_noop!(
+            Messaging::schedule_message(RuntimeOrigin::signed(1), 2, vec![1], 5),
+            Error::<Test>::InvalidDeliveryBlock
+        );
+    });
+}
+
+#[test]
+fn scheduled_message_holds_its_deposit_until_delivered_or_cancelled() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MessageDeposit::set(100);
+        
+        assert_ok!(Messaging::schedule_message(RuntimeOrigin::signed(1), 2, vec![1], 5));
+        let schedule_id = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64, 1u64));
+        assert_eq!(Balances::reserved_balance(1), 100);
+        assert_eq!(Messaging::scheduled_deposits(schedule_id), Some(100));
+        assert_eq!(Messaging::total_message_count(), 1);
+        
+        // Cancelling refunds the deposit and frees the store slot
+        assert_ok!(Messaging::cancel_scheduled_message(RuntimeOrigin::signed(1), schedule_id));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Messaging::scheduled_deposits(schedule_id), None);
+        assert_eq!(Messaging::total_message_count(), 0);
+        
+        // On delivery the deposit carries over to the sent message
+        assert_ok!(Messaging::schedule_message(RuntimeOrigin::signed(1), 2, vec![1], 5));
+        System::set_block_number(5);
+        Messaging::on_initialize(5);
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64));
+        assert_eq!(Messaging::message_deposits(message_id), Some(100));
+        assert_eq!(Balances::reserved_balance(1), 100);
+        assert_eq!(Messaging::total_message_count(), 1);
+    });
+}
+
+#[test]
+fn schedule_message_is_bounded_by_delay_and_store_size() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        assert_noop!(
+            Messaging::schedule_message(
+                RuntimeOrigin::signed(1), 2, vec![1], 2 + MaxScheduleDelay::get()
+            ),
+            Error::<Test>::InvalidDeliveryBlock
+        );
+        assert_ok!(Messaging::schedule_message(
+            RuntimeOrigin::signed(1), 2, vec![1], 1 + MaxScheduleDelay::get()
+        ));
+        
+        // Scheduled messages count towards the store limit
+        MaxTotalMessages::set(1);
+        assert_noop!(
+            Messaging::schedule_message(RuntimeOrigin::signed(1), 3, vec![1], 5),
+            Error::<Test>::MessageStorageFull
+        );
+        assert_noop!(
+            Messaging::send_message(RuntimeOrigin::signed(1), 3, vec![vec![1]]),
+            Error::<Test>::MessageStorageFull
+        );
+    });
+}
+
+#[test]
+fn messages_scheduled_in_the_same_block_are_all_delivered() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        assert_ok!(Messaging::schedule_message(RuntimeOrigin::signed(1), 2, vec![1], 5));
+        assert_ok!(Messaging::schedule_message(RuntimeOrigin::signed(1), 2, vec![2], 5));
+        
+        // The second schedule gets its own id instead of overwriting the first
+        let first_id = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64, 1u64));
+        let second_id = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64, 1u64, 1u32));
+        assert_eq!(Messaging::scheduled_deliveries(5).to_vec(), vec![first_id, second_id]);
+        
+        System::set_block_number(5);
+        Messaging::on_initialize(5);
+        let first_message = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64));
+        let second_message = BlakeTwo256::hash_of(&(1u64, 2u64, 5u64, 1u32));
+        assert_eq!(Messaging::inbox(2).to_vec(), vec![first_message, second_message]);
+        assert_eq!(Messaging::messages(second_message).unwrap().content_cid[0].to_vec(), vec![2]);
+    });
+}
+
+#[test]
+fn extended_message_survives_its_original_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        
+        // Only the sender can change the TTL
+        assert_noop!(
+            Messaging::set_message_ttl(RuntimeOrigin::signed(2), message_id, 200),
+            Error::<Test>::NotAuthorized
+        );
+        
+        assert_ok!(Messaging::set_message_ttl(RuntimeOrigin::signed(1), message_id, 200));
+        System::assert_last_event(Event::MessageTtlUpdated(message_id, 201).into());
+        assert!(!crate::ExpiryIndex::<Test>::contains_key(101));
+        assert_eq!(Messaging::expiry_index(201).to_vec(), vec![message_id]);
+        
+        Messaging::on_initialize(101);
+        assert!(Messaging::messages(message_id).is_some());
+        Messaging::on_initialize(201);
+        assert!(Messaging::messages(message_id).is_none());
+    });
+}
+
+#[test]
+fn message_ttl_is_clamped_and_cannot_revive_expired_messages() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        let message_id = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        
+        // Clamped to MaxMessageTTL (1_000) and MinMessageTTL (10)
+        assert_ok!(Messaging::set_message_ttl(RuntimeOrigin::signed(1), message_id, 5_000));
+        assert_eq!(Messaging::messages(message_id).unwrap().expires_at, 1_001);
+        
+        // Shortening into the past is rejected
+        System::set_block_number(50);
+        assert_noop!(
+            Messaging::set_message_ttl(RuntimeOrigin::signed(1), message_id, 20),
+            Error::<Test>::InvalidTtl
+        );
+        
+        System::set_block_number(5);
+        assert_ok!(Messaging::set_message_ttl(RuntimeOrigin::signed(1), message_id, 1));
+        assert_eq!(Messaging::messages(message_id).unwrap().expires_at, 11);
+        
+        // An expired message cannot be extended
+        System::set_block_number(11);
+        assert_noop!(
+            Messaging::set_message_ttl(RuntimeOrigin::signed(1), message_id, 200),
+            Error::<Test>::MessageAlreadyExpired
+        );
+    });
+}
+
+#[test]
+fn messages_are_pruned_at_their_expiry_block() {
+    new_test_ext().execute_with(|| {
+        // MessageTTL is 100 blocks in the mock
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        let first = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        
+        System::set_block_number(3);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![2]]));
+        let second = BlakeTwo256::hash_of(&(1u64, 2u64, 3u64));
+        
+        assert_eq!(Messaging::expiry_index(101).to_vec(), vec![first]);
+        assert_eq!(Messaging::expiry_index(103).to_vec(), vec![second]);
+        
+        // Nothing expires before its block
+        Messaging::on_initialize(100);
+        assert!(Messaging::messages(first).is_some());
+        
+        Messaging::on_initialize(101);
+        System::assert_last_event(Event::MessageExpired(first).into());
+        assert!(Messaging::messages(first).is_none());
+        assert!(Messaging::messages(second).is_some());
+        assert_eq!(Messaging::inbox(2).to_vec(), vec![second]);
+        assert_eq!(Messaging::outbox(1).to_vec(), vec![second]);
+        assert!(!crate::ExpiryIndex::<Test>::contains_key(101));
+        
+        Messaging::on_initialize(103);
+        assert!(Messaging::messages(second).is_none());
+        assert!(Messaging::inbox(2).is_empty());
+        assert!(!crate::ExpiryIndex::<Test>::contains_key(103));
+    });
+}
+
+#[test]
+fn expired_group_message_leaves_group_indexes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        
+        let owner = 1;
+        let name = b"test group".to_vec();
+        
+        assert_ok!(Messaging::create_group(RuntimeOrigin::signed(owner), name.clone(), vec![2]));
+        let group_id = BlakeTwo256::hash_of(&(owner, name.clone(), 1u64));
+        assert_ok!(Messaging::send_group_message(RuntimeOrigin::signed(owner), group_id, vec![vec![1]]));
+        let message_id = BlakeTwo256::hash_of(&(owner, group_id, 1u64));
+        
+        // A deleted message is dropped from the expiry index right away
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(2), 3, vec![vec![2]]));
+        let direct = BlakeTwo256::hash_of(&(2u64, 3u64, 1u64));
+        assert_ok!(Messaging::delete_message(RuntimeOrigin::signed(2), direct));
+        assert_eq!(Messaging::expiry_index(101).to_vec(), vec![message_id]);
+        
+        Messaging::on_initialize(101);
+        assert!(Messaging::messages(message_id).is_none());
+        assert!(Messaging::group_messages(group_id).is_empty());
+        assert!(Messaging::group_messages_by_sender(group_id, owner).is_empty());
+        assert_eq!(Messaging::group_stats(group_id).message_count, 0);
+    });
+}
+
+#[test]
+fn read_messages_marks_only_eligible_messages() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![1]]));
+        let first = BlakeTwo256::hash_of(&(1u64, 2u64, 1u64));
+        let others = BlakeTwo256::hash_of(&(1u64, 3u64, 1u64));
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 3, vec![vec![2]]));
+        
+        System::set_block_number(2);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![3]]));
+        let second = BlakeTwo256::hash_of(&(1u64, 2u64, 2u64));
+        
+        System::set_block_number(3);
+        assert_ok!(Messaging::send_message(RuntimeOrigin::signed(1), 2, vec![vec![4]]));
+        let already_read = BlakeTwo256::hash_of(&(1u64, 2u64, 3u64));
+        assert_ok!(Messaging::read_message(RuntimeOrigin::signed(2), already_read));
+        assert_eq!(Messaging::unread_count(2), 2);
+        
+        // Unknown, foreign and already read messages are skipped
+        let batch = vec![first, others, second, already_read, H256::repeat_byte(9)];
+        assert_ok!(Messaging::read_messages(RuntimeOrigin::signed(2), batch.try_into().unwrap()));
+        System::assert_last_event(Event::MessagesRead(2).into());
+        System::assert_has_event(Event::MessageReadReceipt(second, 1, 2).into());
+        
+        assert!(Messaging::messages(first).unwrap().read);
+        assert!(Messaging::messages(second).unwrap().read);
+        assert!(!Messaging::messages(others).unwrap().read);
+        assert_eq!(Messaging::unread_count(2), 0);
+        assert_eq!(Messaging::unread_count(3), 1);
+    });
+}
@@ -8,11 +8,25 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn send_message() -> Weight;
     fn read_message() -> Weight;
+    fn read_messages(n: u32) -> Weight;
     fn delete_message() -> Weight;
     fn create_group() -> Weight;
     fn add_member() -> Weight;
     fn remove_member() -> Weight;
     fn send_group_message() -> Weight;
+    fn update_group_read_cursor() -> Weight;
+    fn send_group_reply() -> Weight;
+    fn schedule_message() -> Weight;
+    fn cancel_scheduled_message() -> Weight;
+    fn react() -> Weight;
+    fn set_message_ttl() -> Weight;
+    fn delete_group() -> Weight;
+    fn block_user() -> Weight;
+    fn unblock_user() -> Weight;
+    fn send_reply() -> Weight;
+    fn add_admin() -> Weight;
+    fn remove_admin() -> Weight;
+    fn forward_message() -> Weight;
     fn on_initialize() -> Weight;
 }
 
@@ -21,25 +35,35 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn send_message() -> Weight {
         Weight::from_parts(10_000, 0)
-            .saturating_add(T::DbWeight::get().reads(1))
-            .saturating_add(T::DbWeight::get().writes(3))
+            // up to 8 message id candidates
+            .saturating_add(T::DbWeight::get().reads(11))
+            .saturating_add(T::DbWeight::get().writes(5))
     }
     
     fn read_message() -> Weight {
         Weight::from_parts(5_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    
+    fn read_messages(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
             .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads(n.into()))
             .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes(n.into()))
     }
     
     fn delete_message() -> Weight {
         Weight::from_parts(5_000, 0)
-            .saturating_add(T::DbWeight::get().reads(1))
-            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(6))
     }
     
     fn create_group() -> Weight {
         Weight::from_parts(15_000, 0)
-            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads(9)) // 1 + up to 8 group id candidates
             .saturating_add(T::DbWeight::get().writes(52)) // 1 group + up to 50 memberships + 1 event
     }
     
@@ -57,10 +81,94 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     
     fn send_group_message() -> Weight {
         Weight::from_parts(15_000, 0)
+            // up to 8 message id candidates
+            .saturating_add(T::DbWeight::get().reads(11))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+    
+    fn update_group_read_cursor() -> Weight {
+        Weight::from_parts(8_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    
+    fn send_group_reply() -> Weight {
+        Weight::from_parts(17_000, 0)
+            // up to 8 message id candidates
+            .saturating_add(T::DbWeight::get().reads(12))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+    
+    fn schedule_message() -> Weight {
+        Weight::from_parts(12_000, 0)
+            // up to 8 schedule id candidates + deliveries, count and sender account
+            .saturating_add(T::DbWeight::get().reads(11))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    
+    fn cancel_scheduled_message() -> Weight {
+        Weight::from_parts(10_000, 0)
+            // message, deliveries, count, deposit and sender account
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    
+    fn react() -> Weight {
+        Weight::from_parts(5_000, 0)
             .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    
+    fn set_message_ttl() -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
             .saturating_add(T::DbWeight::get().writes(3))
     }
     
+    fn delete_group() -> Weight {
+        Weight::from_parts(60_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(150))
+    }
+    
+    fn block_user() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads(0))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    
+    fn unblock_user() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads(0))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    
+    fn send_reply() -> Weight {
+        Weight::from_parts(10_000, 0)
+            // up to 8 message id candidates
+            .saturating_add(T::DbWeight::get().reads(12))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    
+    fn add_admin() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    
+    fn remove_admin() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    
+    fn forward_message() -> Weight {
+        Weight::from_parts(10_000, 0)
+            // up to 8 message id candidates
+            .saturating_add(T::DbWeight::get().reads(12))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+    
     fn on_initialize() -> Weight {
         Weight::from_parts(2_000, 0)
     }
@@ -76,6 +184,11 @@ impl WeightInfo for () {
         Weight::from_parts(5_000, 0)
     }
     
+    fn read_messages(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
+    }
+    
     fn delete_message() -> Weight {
         Weight::from_parts(5_000, 0)
     }
@@ -96,6 +209,58 @@ impl WeightInfo for () {
         Weight::from_parts(15_000, 0)
     }
     
+    fn update_group_read_cursor() -> Weight {
+        Weight::from_parts(8_000, 0)
+    }
+    
+    fn send_group_reply() -> Weight {
+        Weight::from_parts(17_000, 0)
+    }
+    
+    fn schedule_message() -> Weight {
+        Weight::from_parts(12_000, 0)
+    }
+    
+    fn cancel_scheduled_message() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn react() -> Weight {
+        Weight::from_parts(5_000, 0)
+    }
+    
+    fn set_message_ttl() -> Weight {
+        Weight::from_parts(5_000, 0)
+    }
+    
+    fn delete_group() -> Weight {
+        Weight::from_parts(60_000_000, 0)
+    }
+    
+    fn block_user() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn unblock_user() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn send_reply() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn add_admin() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn remove_admin() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn forward_message() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
     fn on_initialize() -> Weight {
         Weight::from_parts(2_000, 0)
     }
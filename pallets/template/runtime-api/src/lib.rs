@@ -0,0 +1,48 @@
+//! # Secura Staking Runtime API
+//!
+//! Read-only staking queries for wallets and explorers, answered by the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+pub use pallet_template::{EraIndex, NominatorDashboard, ValidatorStatus};
+use sp_runtime::Perbill;
+
+sp_api::decl_runtime_apis! {
+	/// Staking queries exposed by the runtime.
+	pub trait SecuraStakingApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Rewards `account` can claim now.
+		fn pending_rewards(account: AccountId) -> Balance;
+
+		/// Reward `account` would be credited at the next era rollover, as a validator and as
+		/// a nominator.
+		fn projected_era_reward(account: AccountId) -> Balance;
+
+		/// Nominations, pending rewards and projected next-era reward of `account` in one call.
+		fn nominator_dashboard(account: AccountId) -> NominatorDashboard<AccountId, Balance>;
+
+		/// Annual return a nominator of `validator` earns after its commission.
+		fn validator_effective_apr(validator: AccountId) -> Perbill;
+
+		/// Share of the total stake behind `validator` that is its own stake.
+		fn self_stake_ratio(validator: AccountId) -> Perbill;
+
+		/// What the next era rollover would credit to each account, without applying it.
+		fn simulate_era_rewards() -> Vec<(AccountId, Balance)>;
+
+		/// Whether `account` has rewards to claim, unbonded stake to withdraw or unread
+		/// messages.
+		fn has_claimable(account: AccountId) -> bool;
+
+		/// First era whose offences `validator` can be slashed for once its registration grace
+		/// period is over, or `None` if it is not a validator.
+		fn slashable_from_era(validator: AccountId) -> Option<EraIndex>;
+	}
+}
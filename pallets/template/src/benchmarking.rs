@@ -5,30 +5,412 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Template;
 use frame_benchmarking::v2::*;
+use frame_support::{
+	sp_runtime::{traits::Zero, Perbill, Saturating},
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+};
 use frame_system::RawOrigin;
 
+// Give `who` enough free balance to stake the minimum many times over
+fn fund<T: Config>(who: &T::AccountId) {
+	let amount = T::MinStake::get()
+		.max(T::MinNomination::get())
+		.max(T::Currency::minimum_balance())
+		.saturating_mul(1_000u32.into());
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+// Register a funded, whitelisted and elected validator with `MinStake`, so it passes every
+// check `nominate` makes
+fn setup_validator<T: Config>(who: &T::AccountId) {
+	fund::<T>(who);
+	ValidatorWhitelist::<T>::insert(who, true);
+	Template::<T>::register_validator(RawOrigin::Signed(who.clone()).into(), T::MinStake::get())
+		.expect("funded validator can register");
+	ActiveSet::<T>::mutate(|active| {
+		let _ = active.try_push(who.clone());
+	});
+}
+
+// Nominate `validator` from a funded `nominator` with `MinNomination`
+fn setup_nomination<T: Config>(nominator: &T::AccountId, validator: &T::AccountId) {
+	fund::<T>(nominator);
+	Template::<T>::nominate(
+		RawOrigin::Signed(nominator.clone()).into(),
+		validator.clone(),
+		T::MinNomination::get(),
+	)
+	.expect("funded nominator can nominate");
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32;
+	fn register_validator() {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		ValidatorWhitelist::<T>::insert(&caller, true);
+		let stake = T::MinStake::get();
+
+		#[extrinsic_call]
+		register_validator(RawOrigin::Signed(caller.clone()), stake);
+
+		assert_eq!(Validators::<T>::get(&caller), stake);
+	}
+
+	// Each of the `n` nominations of the validator is unbonded with it
+	#[benchmark]
+	fn remove_validator(n: Linear<0, { T::MaxNominatorsPerValidator::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+		for i in 0..n {
+			setup_nomination::<T>(&account("nominator", i, 0), &caller);
+		}
+
+		#[extrinsic_call]
+		remove_validator(RawOrigin::Signed(caller.clone()));
+
+		assert!(!Validators::<T>::contains_key(&caller));
+		assert!(NominatorsByValidator::<T>::get(&caller).is_empty());
+	}
+
+	#[benchmark]
+	fn nominate() {
+		let validator: T::AccountId = account("validator", 0, 0);
+		setup_validator::<T>(&validator);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let amount = T::MinNomination::get();
+
+		#[extrinsic_call]
+		nominate(RawOrigin::Signed(caller.clone()), validator.clone(), amount);
+
+		assert_eq!(Nominators::<T>::get(&caller).len(), 1);
+	}
+
+	#[benchmark]
+	fn withdraw_nomination() {
+		let validator: T::AccountId = account("validator", 0, 0);
+		setup_validator::<T>(&validator);
+		let caller: T::AccountId = whitelisted_caller();
+		setup_nomination::<T>(&caller, &validator);
+
+		#[extrinsic_call]
+		withdraw_nomination(RawOrigin::Signed(caller.clone()), validator.clone());
+
+		assert!(Nominators::<T>::get(&caller).is_empty());
+	}
+
+	// Restaking the reward into a validator's self stake is the most expensive payout
+	#[benchmark]
+	fn claim_rewards() {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+		Payee::<T>::insert(&caller, RewardDestination::Staked);
+		let rewards = T::Currency::minimum_balance().max(1u32.into());
+		PendingRewards::<T>::insert(&caller, rewards);
+
+		#[extrinsic_call]
+		claim_rewards(RawOrigin::Signed(caller.clone()));
+
+		assert!(PendingRewards::<T>::get(&caller).is_zero());
+	}
+
+	// A malice slash also slashes each of the `n` nominators of the validator
+	#[benchmark]
+	fn slash_validator(n: Linear<0, { T::MaxNominatorsPerValidator::get() }>) -> Result<(), BenchmarkError> {
+		let validator: T::AccountId = account("validator", 0, 0);
+		setup_validator::<T>(&validator);
+		for i in 0..n {
+			setup_nomination::<T>(&account("nominator", i, 0), &validator);
+		}
+		// Move past the registration grace period so the current era is slashable
+		let era = T::SlashGracePeriod::get();
+		CurrentEra::<T>::put(era);
+		let origin = T::SlashOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		slash_validator(origin as T::RuntimeOrigin, validator.clone(), 50, era, SlashCategory::Malice);
+
+		assert!(Validators::<T>::get(&validator) < T::MinStake::get());
+		Ok(())
+	}
+
+	// Each of the `n` accounts has a chunk released early
+	#[benchmark]
+	fn force_unbond_all(n: Linear<1, 1_000>) {
+		let amount = T::MinNomination::get();
+		for i in 0..n {
+			let who: T::AccountId = account("unbonding", i, 0);
+			fund::<T>(&who);
+			T::Currency::reserve(&who, amount).expect("funded account can reserve");
+			Unbonding::<T>::mutate(&who, |chunks| {
+				let _ = chunks.try_push((amount, T::UnbondingPeriod::get()));
+			});
+		}
+
+		#[extrinsic_call]
+		force_unbond_all(RawOrigin::Root, n);
+
+		assert_eq!(Unbonding::<T>::iter().count(), 0);
+	}
+
+	// An era rotation over `v` validators backed by `n` nominations between them, every one
+	// of them rewarded
+	#[benchmark]
+	fn new_era(
+		v: Linear<1, { T::MaxValidators::get() }>,
+		n: Linear<0, { T::MaxNominatorsPerValidator::get() }>,
+	) {
+		let validators: Vec<T::AccountId> = (0..v).map(|i| account("validator", i, 0)).collect();
+		for validator in &validators {
+			// Registrations are capped per era, which this rotation resets anyway
+			NewValidatorsThisEra::<T>::kill();
+			setup_validator::<T>(validator);
+		}
+		for i in 0..n {
+			setup_nomination::<T>(&account("nominator", i, 0), &validators[(i % v) as usize]);
+		}
+		let era = T::RewardStartEra::get();
+		CurrentEra::<T>::put(era);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+
+		#[extrinsic_call]
+		force_new_era(RawOrigin::Root);
+
+		assert_eq!(CurrentEra::<T>::get(), era + 1);
+	}
+
+	// Every unbonding chunk slot is filled and unlocked
+	#[benchmark]
+	fn withdraw_unbonded() {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let amount = T::MinNomination::get();
+		for _ in 0..T::MaxUnbondingChunks::get() {
+			T::Currency::reserve(&caller, amount).expect("funded account can reserve");
+			Unbonding::<T>::mutate(&caller, |chunks| {
+				let _ = chunks.try_push((amount, 0));
+			});
+		}
+
+		#[extrinsic_call]
+		withdraw_unbonded(RawOrigin::Signed(caller.clone()));
+
+		assert!(!Unbonding::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn bond_extra() {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+		let additional = T::MinStake::get();
+
+		#[extrinsic_call]
+		bond_extra(RawOrigin::Signed(caller.clone()), additional);
+
+		assert_eq!(Validators::<T>::get(&caller), additional.saturating_mul(2u32.into()));
+	}
+
+	// The first unbond of the era also records the stake snapshot
+	#[benchmark]
+	fn unbond() {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+		let amount = T::MinStake::get();
+		Template::<T>::bond_extra(RawOrigin::Signed(caller.clone()).into(), amount)
+			.expect("funded validator can bond extra");
+
+		#[extrinsic_call]
+		unbond(RawOrigin::Signed(caller.clone()), amount);
+
+		assert_eq!(Validators::<T>::get(&caller), amount);
+	}
+
+	#[benchmark]
+	fn set_commission() {
 		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+		let commission = Perbill::from_percent(10);
+
+		#[extrinsic_call]
+		set_commission(RawOrigin::Signed(caller.clone()), commission);
+
+		assert_eq!(ValidatorCommission::<T>::get(&caller), commission);
+	}
+
+	#[benchmark]
+	fn chill() {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+
+		#[extrinsic_call]
+		chill(RawOrigin::Signed(caller.clone()));
+
+		assert!(Chilled::<T>::get(&caller));
+	}
+
+	#[benchmark]
+	fn unchill() {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+		Chilled::<T>::insert(&caller, true);
+
+		#[extrinsic_call]
+		unchill(RawOrigin::Signed(caller.clone()));
+
+		assert!(!Chilled::<T>::get(&caller));
+	}
+
+	// Every field at its longest
+	#[benchmark]
+	fn set_validator_metadata() {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_validator::<T>(&caller);
+
+		#[extrinsic_call]
+		set_validator_metadata(
+			RawOrigin::Signed(caller.clone()),
+			vec![b'n'; 64],
+			vec![b'w'; 128],
+			vec![b'd'; 512],
+		);
+
+		assert!(ValidatorMetadata::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn set_payee() {
+		let caller: T::AccountId = whitelisted_caller();
+		let payee = RewardDestination::Account(account("payee", 0, 0));
+
+		#[extrinsic_call]
+		set_payee(RawOrigin::Signed(caller.clone()), payee.clone());
+
+		assert!(Payee::<T>::get(&caller) == payee);
+	}
+
+	#[benchmark]
+	fn add_to_whitelist() {
+		let who: T::AccountId = account("validator", 0, 0);
+
+		#[extrinsic_call]
+		add_to_whitelist(RawOrigin::Root, who.clone());
+
+		assert!(ValidatorWhitelist::<T>::get(&who));
+	}
+
+	#[benchmark]
+	fn remove_from_whitelist() {
+		let who: T::AccountId = account("validator", 0, 0);
+		ValidatorWhitelist::<T>::insert(&who, true);
+
+		#[extrinsic_call]
+		remove_from_whitelist(RawOrigin::Root, who.clone());
+
+		assert!(!ValidatorWhitelist::<T>::get(&who));
+	}
+
+	#[benchmark]
+	fn set_nomination_limit() {
+		let limit = T::MaxNominations::get();
+
+		#[extrinsic_call]
+		set_nomination_limit(RawOrigin::Root, limit);
+
+		assert_eq!(NominationLimit::<T>::get(), Some(limit));
+	}
+
+	// Resuming mints the treasury cut held back while paused
+	#[benchmark]
+	fn set_minting_paused() {
+		MintingPaused::<T>::put(true);
+		let held_cut = T::Currency::minimum_balance().max(1u32.into()).saturating_mul(10u32.into());
+		PendingTreasuryCut::<T>::put(held_cut);
+
+		#[extrinsic_call]
+		set_minting_paused(RawOrigin::Root, false);
+
+		assert!(!MintingPaused::<T>::get());
+		assert!(PendingTreasuryCut::<T>::get().is_zero());
+	}
+
+	#[benchmark]
+	fn report_misbehavior() {
+		let validator: T::AccountId = account("validator", 0, 0);
+		setup_validator::<T>(&validator);
+
+		#[extrinsic_call]
+		report_misbehavior(RawOrigin::Root, validator.clone());
+
+		assert!(UnderInvestigation::<T>::get(&validator));
+	}
+
+	#[benchmark]
+	fn increase_nomination() {
+		let validator: T::AccountId = account("validator", 0, 0);
+		setup_validator::<T>(&validator);
+		let caller: T::AccountId = whitelisted_caller();
+		setup_nomination::<T>(&caller, &validator);
+		let additional = T::MinNomination::get();
+
+		#[extrinsic_call]
+		increase_nomination(RawOrigin::Signed(caller.clone()), validator.clone(), additional);
+
+		assert_eq!(Nominators::<T>::get(&caller)[0].amount, additional.saturating_mul(2u32.into()));
+	}
+
+	#[benchmark]
+	fn decrease_nomination() {
+		let validator: T::AccountId = account("validator", 0, 0);
+		setup_validator::<T>(&validator);
+		let caller: T::AccountId = whitelisted_caller();
+		setup_nomination::<T>(&caller, &validator);
+		let amount = T::MinNomination::get();
+		Template::<T>::increase_nomination(RawOrigin::Signed(caller.clone()).into(), validator.clone(), amount)
+			.expect("funded nominator can increase its nomination");
+
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		decrease_nomination(RawOrigin::Signed(caller.clone()), validator.clone(), amount);
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert_eq!(Nominators::<T>::get(&caller)[0].amount, amount);
+		assert!(Unbonding::<T>::contains_key(&caller));
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
+	fn claim_commission() {
 		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		CommissionEarned::<T>::insert(&caller, T::Currency::minimum_balance().max(1u32.into()));
+
+		#[extrinsic_call]
+		claim_commission(RawOrigin::Signed(caller.clone()));
+
+		assert!(CommissionEarned::<T>::get(&caller).is_zero());
+	}
+
+	// Each of the `n` nominators withdrew while the report was open and is released
+	#[benchmark]
+	fn resolve_report(n: Linear<0, { T::MaxNominatorsPerValidator::get() }>) {
+		let validator: T::AccountId = account("validator", 0, 0);
+		setup_validator::<T>(&validator);
+		let nominators: Vec<T::AccountId> = (0..n).map(|i| account("nominator", i, 0)).collect();
+		for nominator in &nominators {
+			setup_nomination::<T>(nominator, &validator);
+		}
+		UnderInvestigation::<T>::insert(&validator, true);
+		for nominator in &nominators {
+			Template::<T>::withdraw_nomination(RawOrigin::Signed(nominator.clone()).into(), validator.clone())
+				.expect("nominator can withdraw");
+		}
+
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		resolve_report(RawOrigin::Root, validator.clone());
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert!(!UnderInvestigation::<T>::get(&validator));
+		assert_eq!(HeldWithdrawals::<T>::iter_prefix(&validator).count(), 0);
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
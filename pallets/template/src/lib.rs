@@ -4,6 +4,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 pub use pallet::*;
 
 #[cfg(test)]
@@ -19,10 +21,31 @@ pub use weights::*;
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::{pallet_prelude::*, traits::{Currency, ReservableCurrency, Get}};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, ReservableCurrency, WithdrawReasons,
+		},
+	};
 	use frame_system::pallet_prelude::*;
+	use frame_support::sp_runtime::{traits::UniqueSaturatedInto, PerThing, Perbill, Saturating};
+	use alloc::vec::Vec;
 
 	type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+	// Rewards owed to each account
+	type PayoutsOf<T> = Vec<(<T as frame_system::Config>::AccountId, BalanceOf<T>)>;
+
+	// Treasury cut, commission and rewards credited for an era
+	struct EraDistribution<T: Config> {
+		treasury: BalanceOf<T>,
+		commissions: PayoutsOf<T>,
+		rewards: PayoutsOf<T>,
+		// Part of the pool rounding left out of the cut and the payouts
+		residual: BalanceOf<T>,
+	}
 
 	// Simple nominator info structure
 	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -31,6 +54,76 @@ pub mod pallet {
 		pub amount: Balance,
 	}
 
+	// Human-readable identity a validator publishes for explorers and wallets
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo, Default)]
+	pub struct ValidatorInfo {
+		/// Display name
+		pub name: BoundedVec<u8, ConstU32<64>>,
+		/// Website URL
+		pub website: BoundedVec<u8, ConstU32<128>>,
+		/// Free-form description, e.g. the commission the validator intends to charge
+		pub description: BoundedVec<u8, ConstU32<512>>,
+	}
+
+	// Where an account's claimed rewards are paid
+	#[derive(
+		Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo, Default,
+	)]
+	pub enum RewardDestination<AccountId> {
+		/// Free balance of the claiming account
+		#[default]
+		Stash,
+		/// Free balance of another account
+		Account(AccountId),
+		/// Bonded into the claimant's validator stake, or else its first nomination
+		Staked,
+	}
+
+	// What happens to returned stake that would leave its account below the existential
+	// deposit, where it would be reaped
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum DustPolicy<AccountId> {
+		/// The remainder and any free dust go to this account
+		Collect(AccountId),
+		/// The withdrawal is refused and the stake stays reserved
+		KeepAlive,
+	}
+
+	// Kind of offence a validator is slashed for
+	#[derive(
+		Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+	)]
+	pub enum SlashCategory {
+		/// Deliberate misbehavior, nominators are slashed along with the validator
+		Malice,
+		/// Unavailability, only the validator's own stake is slashed
+		Downtime,
+	}
+
+	// Status of a validator as seen by the accounts nominating it
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum ValidatorStatus {
+		/// Registered and eligible for rewards
+		Active,
+		/// No longer registered as a validator
+		NotValidator,
+	}
+
+	// Aggregated nominator-facing view returned by the staking runtime API
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct NominatorDashboard<AccountId, Balance> {
+		/// Total amount bonded across all nominations
+		pub total_bonded: Balance,
+		/// Each nominated validator with the amount bonded to it and its status
+		pub nominations: Vec<(AccountId, Balance, ValidatorStatus)>,
+		/// Rewards waiting to be claimed
+		pub pending_rewards: Balance,
+		/// Reward expected at the next era rollover given current stake
+		pub estimated_next_era_reward: Balance,
+		/// Era of the latest reward claim, `None` if rewards were never claimed
+		pub last_claimed_era: Option<EraIndex>,
+	}
+
 	// Define EraIndex type
 	pub type EraIndex = u32;
 
@@ -55,6 +148,105 @@ pub mod pallet {
 		/// Reward rate per block (as a percentage of total staked)
 		#[pallet::constant]
 		type RewardRate: Get<u32>;
+		/// Pending rewards above which an account is paid out automatically
+		#[pallet::constant]
+		type MaxPendingReward: Get<BalanceOf<Self>>;
+		/// Maximum number of entries in a single batch slash
+		#[pallet::constant]
+		type MaxBatchSlashes: Get<u32>;
+		/// Maximum growth of the era reward pool over the previous era's pool, in percent
+		#[pallet::constant]
+		type MaxRewardGrowth: Get<u32>;
+		/// First era for which rewards are distributed
+		#[pallet::constant]
+		type RewardStartEra: Get<EraIndex>;
+		/// Origin allowed to slash validators
+		type SlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Size of the active validator set elected each era
+		#[pallet::constant]
+		type MaxActiveValidators: Get<u32>;
+		/// Whether only validators in the active set (and their nominators) earn rewards
+		#[pallet::constant]
+		type RewardActiveSetOnly: Get<bool>;
+		/// Whether nominations are only accepted for validators in the active set
+		#[pallet::constant]
+		type RequireActiveValidatorForNomination: Get<bool>;
+		/// Number of eras a validator must spend in the active set before it earns rewards
+		#[pallet::constant]
+		type MinProvingEras: Get<EraIndex>;
+		/// Number of eras after registration during which a validator cannot be slashed
+		#[pallet::constant]
+		type SlashGracePeriod: Get<EraIndex>;
+		/// Maximum number of nominators backing a single validator
+		#[pallet::constant]
+		type MaxNominatorsPerValidator: Get<u32>;
+		/// Number of eras unbonded stake stays reserved before it can be withdrawn
+		#[pallet::constant]
+		type UnbondingPeriod: Get<EraIndex>;
+		/// Maximum number of unbonding chunks per account
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+		/// Minimum number of blocks between two reward claims of an account
+		#[pallet::constant]
+		type MinClaimInterval: Get<BlockNumberFor<Self>>;
+		/// Maximum number of registered validators
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+		/// Maximum number of validators that may register within a single era
+		#[pallet::constant]
+		type MaxNewValidatorsPerEra: Get<u32>;
+		/// Number of eras of reward history kept
+		#[pallet::constant]
+		type HistoryDepth: Get<EraIndex>;
+		/// Whether only whitelisted accounts may register as validators
+		#[pallet::constant]
+		type RequireWhitelist: Get<bool>;
+		/// Consecutive eras a validator may have zero total stake before it is chilled
+		#[pallet::constant]
+		type MaxZeroStakeEras: Get<u32>;
+		/// Fraction of each era's minted reward pool paid to the treasury
+		#[pallet::constant]
+		type TreasuryCut: Get<Perbill>;
+		/// Account receiving the treasury cut
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+		/// How returned stake that would be reaped as dust is handled
+		type DustHandling: Get<DustPolicy<Self::AccountId>>;
+		/// Length of an era in blocks
+		#[pallet::constant]
+		type EraDuration: Get<BlockNumberFor<Self>>;
+		/// Annual inflation paid on validator self stake
+		#[pallet::constant]
+		type ValidatorInflation: Get<Perbill>;
+		/// Annual inflation paid on nominated stake
+		#[pallet::constant]
+		type NominatorInflation: Get<Perbill>;
+		/// Nominators a validator needs to earn rewards, zero disables the requirement
+		#[pallet::constant]
+		type MinNominators: Get<u32>;
+		/// Largest reward bonus for staying bonded without a withdrawal
+		#[pallet::constant]
+		type MaxLoyaltyBonus: Get<Perbill>;
+		/// Eras of continuous bonding over which the loyalty bonus grows to its maximum
+		#[pallet::constant]
+		type LoyaltyRampEras: Get<EraIndex>;
+		/// Number of offences kept in each validator's offence history
+		#[pallet::constant]
+		type MaxOffenceHistory: Get<u32>;
+		/// Commission rate above which a validator does not earn its commission, one disables
+		/// the cap
+		#[pallet::constant]
+		type SoftCommissionCap: Get<Perbill>;
+		/// Whether commission above `SoftCommissionCap` goes to the validator's nominators
+		/// instead of never being minted
+		#[pallet::constant]
+		type RedirectExcessCommission: Get<bool>;
+		/// Smallest share of its total stake a validator must hold itself after a slash,
+		/// below which it is chilled. Zero disables the check
+		#[pallet::constant]
+		type MinSelfStakeRatio: Get<Perbill>;
+		/// Handler for slashed funds, burned when `()`
+		type OnSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
 		/// A type representing the weights required by the dispatchables of this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -81,6 +273,17 @@ pub mod pallet {
 		ValueQuery
 	>;
 
+	// Nominators backing each validator
+	#[pallet::storage]
+	#[pallet::getter(fn nominators_by_validator)]
+	pub type NominatorsByValidator<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxNominatorsPerValidator>,
+		ValueQuery
+	>;
+
 	// Total stake for each validator (self + nominations)
 	#[pallet::storage]
 	#[pallet::getter(fn total_validator_stake)]
@@ -97,6 +300,16 @@ pub mod pallet {
 	#[pallet::getter(fn validator_count)]
 	pub type ValidatorCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+	// Number of validators registered in the current era
+	#[pallet::storage]
+	#[pallet::getter(fn new_validators_this_era)]
+	pub type NewValidatorsThisEra<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	// Number of accounts with at least one nomination
+	#[pallet::storage]
+	#[pallet::getter(fn nominator_count)]
+	pub type NominatorCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	// Total staked amount
 	#[pallet::storage]
 	#[pallet::getter(fn total_staked)]
@@ -113,6 +326,37 @@ pub mod pallet {
 		ValueQuery
 	>;
 
+	// Block of each account's latest reward claim
+	#[pallet::storage]
+	#[pallet::getter(fn last_claim_block)]
+	pub type LastClaimBlock<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+	>;
+
+	// Era of each account's latest reward claim
+	#[pallet::storage]
+	#[pallet::getter(fn last_claimed_era)]
+	pub type LastClaimedEra<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		EraIndex,
+	>;
+
+	// Reward destination of each account
+	#[pallet::storage]
+	#[pallet::getter(fn payee)]
+	pub type Payee<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		RewardDestination<T::AccountId>,
+		ValueQuery,
+	>;
+
 	// Current era index
 	#[pallet::storage]
 	#[pallet::getter(fn current_era)]
@@ -123,6 +367,232 @@ pub mod pallet {
 	#[pallet::getter(fn era_start_block)]
 	pub type EraStartBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+	// Validators with the highest total stake, elected at the start of each era
+	#[pallet::storage]
+	#[pallet::getter(fn active_set)]
+	pub type ActiveSet<T: Config> = StorageValue<
+		_,
+		BoundedVec<T::AccountId, T::MaxActiveValidators>,
+		ValueQuery
+	>;
+
+	// Era in which each validator was first elected to the active set
+	#[pallet::storage]
+	#[pallet::getter(fn first_active_era)]
+	pub type FirstActiveEra<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		EraIndex,
+		OptionQuery
+	>;
+
+	// Era in which each validator registered, kept after its removal until its last stake
+	// snapshot is pruned
+	#[pallet::storage]
+	#[pallet::getter(fn registered_at_era)]
+	pub type RegisteredAtEra<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		EraIndex,
+		ValueQuery
+	>;
+
+	// Reward pool distributed in the previous era
+	#[pallet::storage]
+	#[pallet::getter(fn prev_era_pool)]
+	pub type PrevEraPool<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	// Whether reward minting is paused, rewards keep accruing while it is
+	#[pallet::storage]
+	#[pallet::getter(fn minting_paused)]
+	pub type MintingPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	// Treasury cut accrued while minting is paused, paid when it resumes
+	#[pallet::storage]
+	#[pallet::getter(fn pending_treasury_cut)]
+	pub type PendingTreasuryCut<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	// Metadata published by each validator
+	#[pallet::storage]
+	#[pallet::getter(fn validator_metadata)]
+	pub type ValidatorMetadata<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		ValidatorInfo,
+		OptionQuery
+	>;
+
+	// Number of nominations an account may hold, lowered by governance below `MaxNominations`
+	#[pallet::storage]
+	#[pallet::getter(fn nomination_limit)]
+	pub type NominationLimit<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	// Share of its nominators' rewards each validator charges
+	#[pallet::storage]
+	#[pallet::getter(fn validator_commission)]
+	pub type ValidatorCommission<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Perbill,
+		ValueQuery
+	>;
+
+	// Commission each validator has earned and not yet claimed, kept apart from its rewards
+	#[pallet::storage]
+	#[pallet::getter(fn commission_earned)]
+	pub type CommissionEarned<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// Stake waiting out the unbonding period as (amount, unlock era) chunks
+	#[pallet::storage]
+	#[pallet::getter(fn unbonding)]
+	pub type Unbonding<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(BalanceOf<T>, EraIndex), T::MaxUnbondingChunks>,
+		ValueQuery
+	>;
+
+	// Account a paged `force_unbond_all` stopped at, the next call resumes after it
+	#[pallet::storage]
+	#[pallet::getter(fn force_unbond_cursor)]
+	pub type ForceUnbondCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	// Validators with an open misbehavior report
+	#[pallet::storage]
+	#[pallet::getter(fn under_investigation)]
+	pub type UnderInvestigation<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery
+	>;
+
+	// Consecutive eras each validator has ended with zero total stake
+	#[pallet::storage]
+	#[pallet::getter(fn zero_stake_eras)]
+	pub type ZeroStakeEras<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery
+	>;
+
+	// Latest offences of each validator as (offence era, slash percent), oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn offence_history)]
+	pub type OffenceHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(EraIndex, u32), T::MaxOffenceHistory>,
+		ValueQuery,
+	>;
+
+	// Number of offences each validator has been slashed for while registered
+	#[pallet::storage]
+	#[pallet::getter(fn offence_count)]
+	pub type OffenceCount<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	// Era since which each staker has been bonded without a withdrawal
+	#[pallet::storage]
+	#[pallet::getter(fn bonded_since_era)]
+	pub type BondedSinceEra<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		EraIndex,
+	>;
+
+	// Accounts approved to register as validators when `RequireWhitelist` is set
+	#[pallet::storage]
+	#[pallet::getter(fn validator_whitelist)]
+	pub type ValidatorWhitelist<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery
+	>;
+
+	// Validators that paused validating, keeping their stake and nominations
+	#[pallet::storage]
+	#[pallet::getter(fn chilled)]
+	pub type Chilled<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery
+	>;
+
+	// Nominations withdrawn during an investigation, kept reserved until it is resolved
+	#[pallet::storage]
+	#[pallet::getter(fn held_withdrawals)]
+	pub type HeldWithdrawals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// Rewards and commission each account earned in an era, kept for `HistoryDepth` eras
+	#[pallet::storage]
+	#[pallet::getter(fn era_rewards)]
+	pub type EraRewards<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		EraIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// Total rewards and commission distributed for an era, kept for `HistoryDepth` eras
+	#[pallet::storage]
+	#[pallet::getter(fn era_total_rewards)]
+	pub type EraTotalRewards<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		EraIndex,
+		BalanceOf<T>,
+		ValueQuery
+	>;
+
+	// Self stake of each validator in an era, kept while offences in that era can be slashed
+	#[pallet::storage]
+	#[pallet::getter(fn era_stake_snapshot)]
+	pub type EraStakeSnapshot<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		EraIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -138,8 +608,68 @@ pub mod pallet {
 		RewardsClaimed(T::AccountId, BalanceOf<T>),
 		/// Rewards have been distributed [block_number, total_rewards]
 		RewardsDistributed(EraIndex, BalanceOf<T>),
-		/// A validator has been slashed [validator, amount, percentage]
-		ValidatorSlashed(T::AccountId, BalanceOf<T>, u32),
+		/// A validator has been slashed [validator, amount, percentage, category]
+		ValidatorSlashed(T::AccountId, BalanceOf<T>, u32, SlashCategory),
+		/// A batch slash has been applied [validators_slashed, total_amount]
+		ValidatorsSlashed(u32, BalanceOf<T>),
+		/// A validator has added to their self stake [validator, additional]
+		ValidatorBondedExtra(T::AccountId, BalanceOf<T>),
+		/// A validator's self stake has grown, for its nominators [validator, new_self_stake]
+		ValidatorStakeIncreased(T::AccountId, BalanceOf<T>),
+		/// A validator has reduced their self stake [validator, amount]
+		ValidatorUnbonded(T::AccountId, BalanceOf<T>),
+		/// A validator has been reported for misbehavior [validator]
+		MisbehaviorReported(T::AccountId),
+		/// A misbehavior report has been resolved [validator]
+		ReportResolved(T::AccountId),
+		/// A withdrawal is held until the validator's report is resolved [nominator, validator, amount]
+		WithdrawalHeld(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A validator below the minimum stake has been removed [validator, stake]
+		ValidatorDeactivated(T::AccountId, BalanceOf<T>),
+		/// A slashed validator's nominators are notified [validator, amount, nominator_count]
+		ValidatorSlashedWithBackers(T::AccountId, BalanceOf<T>, u32),
+		/// An era ended before rewards start and paid nothing [era]
+		RewardsNotYetStarted(EraIndex),
+		/// Reward minting has been paused or resumed [paused]
+		MintingPausedSet(bool),
+		/// A validator has set its commission [validator, commission]
+		CommissionSet(T::AccountId, Perbill),
+		/// Stake has started unbonding [account, amount, unlock_era]
+		Unbonded(T::AccountId, BalanceOf<T>, EraIndex),
+		/// Unbonded stake has been withdrawn [account, amount]
+		Withdrawn(T::AccountId, BalanceOf<T>),
+		/// Unbonding stake was released early by governance [account, amount]
+		EmergencyUnbonded(T::AccountId, BalanceOf<T>),
+		/// A nomination has been slashed along with its validator [nominator, validator, amount]
+		NominatorSlashed(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Part of an era's pool was left undistributed by rounding and paid to the treasury [era, amount]
+		RewardResidual(EraIndex, BalanceOf<T>),
+		/// A validator has paused validating [validator]
+		ValidatorChilled(T::AccountId),
+		/// A chilled validator has resumed validating [validator]
+		ValidatorUnchilled(T::AccountId),
+		/// A validator has claimed its earned commission [validator, amount]
+		CommissionClaimed(T::AccountId, BalanceOf<T>),
+		/// An account has been approved to register as a validator [account]
+		AddedToWhitelist(T::AccountId),
+		/// An account's approval to register as a validator has been withdrawn [account]
+		RemovedFromWhitelist(T::AccountId),
+		/// A new era has started [era, start_block]
+		NewEra(EraIndex, BlockNumberFor<T>),
+		/// The treasury has received its cut of an era's rewards [era, amount]
+		TreasuryFunded(EraIndex, BalanceOf<T>),
+		/// An account has set where its rewards are paid [account, destination]
+		PayeeSet(T::AccountId, RewardDestination<T::AccountId>),
+		/// Governance has set the number of nominations an account may hold [limit]
+		NominationLimitSet(u32),
+		/// A validator has published new metadata [validator]
+		MetadataUpdated(T::AccountId),
+		/// Returned stake below the existential deposit was collected [account, collector, amount]
+		DustCollected(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A nomination has been increased [nominator, validator, new amount]
+		NominationIncreased(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A nomination has been decreased [nominator, validator, new amount]
+		NominationDecreased(T::AccountId, T::AccountId, BalanceOf<T>),
 	}
 
 	#[pallet::error]
@@ -168,25 +698,102 @@ pub mod pallet {
 		ZeroSlashAmount,
 		/// Insufficient stake for slashing
 		InsufficientStake,
+		/// Too many entries in a batch slash
+		TooManySlashes,
+		/// Validator already has an open misbehavior report
+		AlreadyUnderInvestigation,
+		/// Validator has no open misbehavior report
+		NotUnderInvestigation,
+		/// Reward minting is paused
+		MintingPaused,
+		/// Too many unbonding chunks
+		TooManyUnbondingChunks,
+		/// No unbonded stake has reached its unlock era
+		NothingUnlocked,
+		/// Validator already has the maximum number of nominators
+		ValidatorOversubscribed,
+		/// Pending rewards are below the existential deposit of an account that does not exist
+		RewardBelowExistential,
+		/// Validator is already chilled
+		AlreadyChilled,
+		/// Validator is not chilled
+		NotChilled,
+		/// No commission to claim
+		NoCommission,
+		/// Rewards were claimed too recently
+		ClaimTooSoon,
+		/// The maximum number of validators is registered
+		TooManyValidators,
+		/// The maximum number of validators has registered in the current era
+		TooManyNewValidatorsThisEra,
+		/// Offence era is in the future or older than the unbonding period
+		InvalidOffenceEra,
+		/// Account is not whitelisted to register as a validator
+		NotWhitelisted,
+		/// Offence was committed within the validator's registration grace period
+		InSlashGracePeriod,
+		/// Validator is not in the active set
+		ValidatorNotActive,
+		/// A validator metadata field is longer than its bound
+		MetadataFieldTooLong,
+		/// Returning the stake would leave the account below the existential deposit
+		WithdrawalWouldReap,
+		/// Nomination limit is above `MaxNominations`
+		NominationLimitTooHigh,
+		/// Account holds no more nominations than the nomination limit
+		WithinNominationLimit,
+		/// Validator has an open misbehavior report
+		ValidatorUnderInvestigation,
+		/// Validator's self stake is too small a share of its total stake
+		SelfStakeRatioTooLow,
+		/// A page of a sweep must handle at least one account
+		ZeroLimit,
+	}
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Validators registered at genesis with their self stake
+		pub initial_validators: Vec<(T::AccountId, BalanceOf<T>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (validator, stake) in self.initial_validators.iter() {
+				assert!(*stake >= T::MinStake::get(), "Initial validator stake is below MinStake");
+				assert!(!Validators::<T>::contains_key(validator), "Initial validator is listed twice");
+				assert!(
+					ValidatorCount::<T>::get() < T::MaxValidators::get(),
+					"Too many initial validators"
+				);
+				T::Currency::reserve(validator, *stake).expect("Initial validator cannot reserve its stake");
+				
+				Validators::<T>::insert(validator, *stake);
+				TotalValidatorStake::<T>::insert(validator, *stake);
+				BondedSinceEra::<T>::insert(validator, 0);
+				ValidatorCount::<T>::mutate(|count| *count += 1);
+				TotalStaked::<T>::mutate(|total| *total = total.saturating_add(*stake));
+			}
+			
+			// Initial validators validate from the first era
+			Pallet::<T>::elect_active_set();
+			Pallet::<T>::snapshot_stakes(0);
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			// Check if it's time for a new era
-			let era_duration = BlockNumberFor::<T>::from(Self::ERA_DURATION);
+			let era_duration = T::EraDuration::get();
 			let current_era = Self::current_era();
 			let era_start_block = Self::era_start_block();
 			
 			if n >= era_start_block + era_duration {
-				// Start a new era
-				CurrentEra::<T>::put(current_era + 1);
-				EraStartBlock::<T>::put(n);
-				
-				// Distribute rewards for the previous era
-				Self::distribute_rewards(current_era);
-				
-				Weight::from_parts(10_000_000, 0)
+				let weight = Self::era_rotation_weight();
+				Self::start_new_era(current_era, n);
+				weight
 			} else {
 				Weight::from_parts(1_000_000, 0)
 			}
@@ -197,7 +804,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Register as a validator with the specified stake
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::do_something())]
+		#[pallet::weight(T::WeightInfo::register_validator())]
 		pub fn register_validator(
 			origin: OriginFor<T>,
 			#[pallet::compact] stake: BalanceOf<T>
@@ -207,9 +814,22 @@ pub mod pallet {
 			// Check if already a validator
 			ensure!(!Validators::<T>::contains_key(&who), Error::<T>::AlreadyValidator);
 			
+			// Check the whitelist on permissioned chains
+			ensure!(
+				!T::RequireWhitelist::get() || ValidatorWhitelist::<T>::get(&who),
+				Error::<T>::NotWhitelisted
+			);
+			
 			// Check minimum stake
 			ensure!(stake >= T::MinStake::get(), Error::<T>::StakeBelowMinimum);
 			
+			// Check the validator cap, counting registrations earlier in the same block
+			ensure!(ValidatorCount::<T>::get() < T::MaxValidators::get(), Error::<T>::TooManyValidators);
+			ensure!(
+				NewValidatorsThisEra::<T>::get() < T::MaxNewValidatorsPerEra::get(),
+				Error::<T>::TooManyNewValidatorsThisEra
+			);
+			
 			// Check balance
 			ensure!(T::Currency::free_balance(&who) >= stake, Error::<T>::InsufficientBalance);
 			
@@ -221,9 +841,12 @@ pub mod pallet {
 			
 			// Initialize total validator stake (self stake + nominations)
 			TotalValidatorStake::<T>::insert(&who, stake);
+			RegisteredAtEra::<T>::insert(&who, Self::current_era());
+			Self::note_bonded(&who);
 			
 			// Increment validator count
 			ValidatorCount::<T>::mutate(|count| *count += 1);
+			NewValidatorsThisEra::<T>::mutate(|count| *count += 1);
 			
 			// Update total staked
 			let old_total = TotalStaked::<T>::get();
@@ -236,49 +859,243 @@ pub mod pallet {
 			Ok(())
 		}
 		
-		/// Remove validator status and return stake
-		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
-		pub fn remove_validator(origin: OriginFor<T>) -> DispatchResult {
+		/// Withdraw all unbonding chunks whose unlock era has been reached
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			
-			// Check if is a validator and get stake
-			let stake = Validators::<T>::get(&who);
-			ensure!(stake > BalanceOf::<T>::zero(), Error::<T>::NotValidator);
-			
-			// Unreserve the stake
-			T::Currency::unreserve(&who, stake);
-			
-			// Remove validator
-			Validators::<T>::remove(&who);
-			TotalValidatorStake::<T>::remove(&who);
+			let current_era = Self::current_era();
+			let mut chunks = Unbonding::<T>::get(&who);
+			let mut unlocked = BalanceOf::<T>::zero();
+			chunks.retain(|(amount, unlock_era)| {
+				if *unlock_era <= current_era {
+					unlocked = unlocked.saturating_add(*amount);
+					false
+				} else {
+					true
+				}
+			});
+			ensure!(!unlocked.is_zero(), Error::<T>::NothingUnlocked);
 			
-			// Decrement validator count
-			ValidatorCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+			if chunks.is_empty() {
+				Unbonding::<T>::remove(&who);
+			} else {
+				Unbonding::<T>::insert(&who, chunks);
+			}
 			
-			// Update total staked
-			let old_total = TotalStaked::<T>::get();
-			let new_total = old_total.checked_sub(&stake).unwrap_or(old_total);
-			TotalStaked::<T>::put(new_total);
+			// A slash may have taken part of the reserve in the meantime
+			let withdrawn = Self::return_funds(&who, unlocked)?;
 			
-			// Emit event
-			Self::deposit_event(Event::ValidatorRemoved(who, stake));
+			Self::deposit_event(Event::Withdrawn(who, withdrawn));
 			
 			Ok(())
 		}
-
-		/// Nominate a validator with the specified amount
-		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::do_something())]
-		pub fn nominate(
-			origin: OriginFor<T>,
-			validator: T::AccountId,
-			#[pallet::compact] amount: BalanceOf<T>
-		) -> DispatchResult {
+		
+		/// Release every unbonding chunk of every account immediately, ignoring the unbonding
+		/// period. A break-glass operation for recovering from a chain halt. Each call handles
+		/// at most `limit` accounts and the next call carries on where it stopped
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::force_unbond_all(*limit))]
+		pub fn force_unbond_all(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(limit > 0, Error::<T>::ZeroLimit);
+			
+			// Resume after the last account handled, so accounts that keep their chunks are
+			// not visited again by every page
+			let unbonding: Vec<_> = match ForceUnbondCursor::<T>::take() {
+				Some(last) => Unbonding::<T>::iter_from(Unbonding::<T>::hashed_key_for(&last))
+					.take(limit as usize)
+					.collect(),
+				None => Unbonding::<T>::iter().take(limit as usize).collect(),
+			};
+			if unbonding.len() == limit as usize {
+				if let Some((last, _)) = unbonding.last() {
+					ForceUnbondCursor::<T>::put(last);
+				}
+			}
+			
+			for (who, chunks) in unbonding {
+				let unlocked = chunks
+					.iter()
+					.fold(BalanceOf::<T>::zero(), |sum, (amount, _)| sum.saturating_add(*amount));
+				// Accounts the release would reap keep their chunks
+				let Ok(withdrawn) = Self::return_funds(&who, unlocked) else {
+					continue;
+				};
+				Unbonding::<T>::remove(&who);
+				
+				Self::deposit_event(Event::EmergencyUnbonded(who, withdrawn));
+			}
+			
+			Ok(())
+		}
+		
+		/// Set the commission the caller charges its nominators
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_commission())]
+		pub fn set_commission(origin: OriginFor<T>, commission: Perbill) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			
-			// Check if validator exists
-			ensure!(Validators::<T>::contains_key(&validator), Error::<T>::NotValidator);
+			ensure!(Validators::<T>::contains_key(&who), Error::<T>::NotValidator);
+			
+			ValidatorCommission::<T>::insert(&who, commission);
+			
+			Self::deposit_event(Event::CommissionSet(who, commission));
+			
+			Ok(())
+		}
+		
+		/// Remove validator status and return stake
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_validator(T::MaxNominatorsPerValidator::get()))]
+		pub fn remove_validator(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			// Check if is a validator and get stake
+			let stake = Validators::<T>::get(&who);
+			ensure!(stake > BalanceOf::<T>::zero(), Error::<T>::NotValidator);
+			
+			Self::do_remove_validator(&who, stake)?;
+			
+			// Emit event
+			Self::deposit_event(Event::ValidatorRemoved(who, stake));
+			
+			Ok(())
+		}
+
+		/// Remove a validator on behalf of governance, unbonding its stake
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::remove_validator(T::MaxNominatorsPerValidator::get()))]
+		pub fn force_remove_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+			
+			let stake = Validators::<T>::get(&validator);
+			ensure!(stake > BalanceOf::<T>::zero(), Error::<T>::NotValidator);
+			
+			Self::do_remove_validator(&validator, stake)?;
+			
+			Self::deposit_event(Event::ValidatorRemoved(validator, stake));
+			
+			Ok(())
+		}
+
+		/// Add to the caller's validator self stake
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::bond_extra())]
+		pub fn bond_extra(
+			origin: OriginFor<T>,
+			#[pallet::compact] additional: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			ensure!(Validators::<T>::contains_key(&who), Error::<T>::NotValidator);
+			
+			// Reserve the additional stake
+			T::Currency::reserve(&who, additional).map_err(|_| Error::<T>::InsufficientBalance)?;
+			
+			// Update self stake and total validator stake
+			let self_stake = Validators::<T>::mutate(&who, |stake| {
+				*stake = stake.checked_add(&additional).unwrap_or(*stake);
+				*stake
+			});
+			TotalValidatorStake::<T>::mutate(&who, |total| {
+				*total = total.checked_add(&additional).unwrap_or(*total);
+			});
+			
+			// Update total staked
+			let old_total = TotalStaked::<T>::get();
+			let new_total = old_total.checked_add(&additional).unwrap_or(old_total);
+			TotalStaked::<T>::put(new_total);
+			
+			Self::deposit_event(Event::ValidatorBondedExtra(who.clone(), additional));
+			Self::deposit_event(Event::ValidatorStakeIncreased(who, self_stake));
+			
+			Ok(())
+		}
+
+		/// Reduce the caller's validator self stake, keeping at least `MinStake`. The amount is
+		/// released after the unbonding period
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::unbond())]
+		pub fn unbond(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			ensure!(Validators::<T>::contains_key(&who), Error::<T>::NotValidator);
+			let remaining = Validators::<T>::get(&who)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::InsufficientStake)?;
+			ensure!(remaining >= T::MinStake::get(), Error::<T>::StakeBelowMinimum);
+			
+			Self::note_stake_snapshot(&who);
+			Self::schedule_unbond(&who, amount)?;
+			Self::reset_bonded_since(&who);
+			
+			// Update self stake and total validator stake
+			Validators::<T>::insert(&who, remaining);
+			TotalValidatorStake::<T>::mutate(&who, |total| {
+				*total = total.checked_sub(&amount).unwrap_or(*total);
+			});
+			
+			// Update total staked
+			let old_total = TotalStaked::<T>::get();
+			let new_total = old_total.checked_sub(&amount).unwrap_or(old_total);
+			TotalStaked::<T>::put(new_total);
+			
+			Self::deposit_event(Event::ValidatorUnbonded(who, amount));
+			
+			Ok(())
+		}
+
+		/// Pause validating. A chilled validator is not elected and neither it nor its
+		/// nominators earn rewards, but its stake and nominations are kept
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::chill())]
+		pub fn chill(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			ensure!(Validators::<T>::contains_key(&who), Error::<T>::NotValidator);
+			ensure!(!Chilled::<T>::get(&who), Error::<T>::AlreadyChilled);
+			
+			Chilled::<T>::insert(&who, true);
+			
+			Self::deposit_event(Event::ValidatorChilled(who));
+			
+			Ok(())
+		}
+
+		/// Resume validating after `chill`. The validator is considered again at the next
+		/// election and reward distribution. Requires a self stake of at least `MinStake`
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::unchill())]
+		pub fn unchill(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			ensure!(Chilled::<T>::get(&who), Error::<T>::NotChilled);
+			ensure!(Validators::<T>::get(&who) >= T::MinStake::get(), Error::<T>::StakeBelowMinimum);
+			ensure!(!Self::below_min_self_stake_ratio(&who), Error::<T>::SelfStakeRatioTooLow);
+			
+			Chilled::<T>::remove(&who);
+			
+			Self::deposit_event(Event::ValidatorUnchilled(who));
+			
+			Ok(())
+		}
+
+		/// Nominate a validator with the specified amount
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::nominate())]
+		pub fn nominate(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			#[pallet::compact] amount: BalanceOf<T>
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			Self::ensure_nominatable(&validator)?;
 			
 			// Check minimum nomination
 			ensure!(amount >= T::MinNomination::get(), Error::<T>::NominationBelowMinimum);
@@ -290,8 +1107,8 @@ pub mod pallet {
 			let mut nominations = Nominators::<T>::get(&who);
 			ensure!(!nominations.iter().any(|n| n.validator == validator), Error::<T>::AlreadyNominated);
 			
-			// Check max nominations
-			ensure!(nominations.len() < T::MaxNominations::get() as usize, Error::<T>::MaxNominationsReached);
+			// Check max nominations, which also holds back accounts over a lowered limit
+			ensure!(nominations.len() < Self::max_nominations() as usize, Error::<T>::MaxNominationsReached);
 			
 			// Reserve the amount
 			T::Currency::reserve(&who, amount)?;
@@ -302,7 +1119,8 @@ pub mod pallet {
 				amount,
 			};
 			nominations.try_push(nomination).map_err(|_| Error::<T>::MaxNominationsReached)?;
-			Nominators::<T>::insert(&who, nominations);
+			Self::put_nominations(&who, nominations)?;
+			Self::note_bonded(&who);
 			
 			// Update total validator stake
 			TotalValidatorStake::<T>::mutate(&validator, |total| {
@@ -322,61 +1140,202 @@ pub mod pallet {
 
 		/// Withdraw a nomination from a validator
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
+		#[pallet::weight(T::WeightInfo::withdraw_nomination())]
 		pub fn withdraw_nomination(
 			origin: OriginFor<T>,
 			validator: T::AccountId
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			
-			// Get nominations
-			let mut nominations = Nominators::<T>::get(&who);
+			Self::do_withdraw_nomination(who, validator, false)
+		}
+		
+		/// Move the caller's nomination from one validator to another without unbonding it
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::nominate().saturating_add(T::WeightInfo::withdraw_nomination()))]
+		pub fn re_nominate(
+			origin: OriginFor<T>,
+			from_validator: T::AccountId,
+			to_validator: T::AccountId
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 			
-			// Find the nomination
-			let position = nominations.iter().position(|n| n.validator == validator)
+			ensure!(Validators::<T>::contains_key(&from_validator), Error::<T>::NotValidator);
+			
+			// Stake behind a validator under investigation stays put until resolution
+			ensure!(!UnderInvestigation::<T>::get(&from_validator), Error::<T>::ValidatorUnderInvestigation);
+			
+			// The new validator must be able to take the nomination as `nominate` would
+			Self::ensure_nominatable(&to_validator)?;
+			
+			let mut nominations = Nominators::<T>::get(&who);
+			let position = nominations.iter().position(|n| n.validator == from_validator)
 				.ok_or(Error::<T>::NominationNotFound)?;
+			ensure!(!nominations.iter().any(|n| n.validator == to_validator), Error::<T>::AlreadyNominated);
 			
-			// Get the nomination amount
+			// The funds stay reserved, so `TotalStaked` does not change
 			let amount = nominations[position].amount;
+			nominations[position].validator = to_validator.clone();
+			Self::put_nominations(&who, nominations)?;
 			
-			// Remove the nomination
-			nominations.swap_remove(position);
+			TotalValidatorStake::<T>::mutate(&from_validator, |total| {
+				*total = total.checked_sub(&amount).unwrap_or(*total);
+			});
+			TotalValidatorStake::<T>::mutate(&to_validator, |total| {
+				*total = total.checked_add(&amount).unwrap_or(*total);
+			});
+			
+			Self::deposit_event(Event::NominationWithdrawn(who.clone(), from_validator, amount));
+			Self::deposit_event(Event::Nomination(who, to_validator, amount));
+			
+			Ok(())
+		}
+		
+		/// Reserve `additional` more behind the caller's nomination of `validator`
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::increase_nomination())]
+		pub fn increase_nomination(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			#[pallet::compact] additional: BalanceOf<T>
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			Self::ensure_nominatable(&validator)?;
+			
+			let mut nominations = Nominators::<T>::get(&who);
+			let nomination = nominations.iter_mut().find(|n| n.validator == validator)
+				.ok_or(Error::<T>::NominationNotFound)?;
+			
+			T::Currency::reserve(&who, additional).map_err(|_| Error::<T>::InsufficientBalance)?;
+			
+			nomination.amount = nomination.amount.saturating_add(additional);
+			let new_amount = nomination.amount;
 			Nominators::<T>::insert(&who, nominations);
 			
-			// Unreserve the amount
-			T::Currency::unreserve(&who, amount);
+			TotalValidatorStake::<T>::mutate(&validator, |total| {
+				*total = total.checked_add(&additional).unwrap_or(*total);
+			});
+			let old_total = TotalStaked::<T>::get();
+			TotalStaked::<T>::put(old_total.checked_add(&additional).unwrap_or(old_total));
+			
+			Self::deposit_event(Event::NominationIncreased(who, validator, new_amount));
+			
+			Ok(())
+		}
+		
+		/// Reduce the caller's nomination of `validator` by `amount`, keeping at least
+		/// `MinNomination`. The amount is released after the unbonding period
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::decrease_nomination())]
+		pub fn decrease_nomination(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			#[pallet::compact] amount: BalanceOf<T>
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			let mut nominations = Nominators::<T>::get(&who);
+			let nomination = nominations.iter_mut().find(|n| n.validator == validator)
+				.ok_or(Error::<T>::NominationNotFound)?;
+			let remaining = nomination.amount
+				.checked_sub(&amount)
+				.filter(|remaining| *remaining >= T::MinNomination::get())
+				.ok_or(Error::<T>::NominationBelowMinimum)?;
+			
+			nomination.amount = remaining;
+			Nominators::<T>::insert(&who, nominations);
+			Self::reset_bonded_since(&who);
 			
-			// Update total validator stake
 			TotalValidatorStake::<T>::mutate(&validator, |total| {
 				*total = total.checked_sub(&amount).unwrap_or(*total);
 			});
-			
-			// Update total staked
 			let old_total = TotalStaked::<T>::get();
-			let new_total = old_total.checked_sub(&amount).unwrap_or(old_total);
-			TotalStaked::<T>::put(new_total);
+			TotalStaked::<T>::put(old_total.checked_sub(&amount).unwrap_or(old_total));
 			
-			// Emit event
-			Self::deposit_event(Event::NominationWithdrawn(who, validator, amount));
+			// While the validator is under investigation the funds stay reserved until resolution
+			if UnderInvestigation::<T>::get(&validator) {
+				HeldWithdrawals::<T>::mutate(&validator, &who, |held| {
+					*held = held.saturating_add(amount);
+				});
+				Self::deposit_event(Event::WithdrawalHeld(who.clone(), validator.clone(), amount));
+			} else {
+				Self::schedule_unbond(&who, amount)?;
+			}
+			
+			Self::deposit_event(Event::NominationDecreased(who, validator, remaining));
+			
+			Ok(())
+		}
+		
+		/// Publish the caller's validator name, website and description, replacing any
+		/// earlier metadata
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::set_validator_metadata())]
+		pub fn set_validator_metadata(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			website: Vec<u8>,
+			description: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			ensure!(Validators::<T>::contains_key(&who), Error::<T>::NotValidator);
+			
+			let info = ValidatorInfo {
+				name: name.try_into().map_err(|_| Error::<T>::MetadataFieldTooLong)?,
+				website: website.try_into().map_err(|_| Error::<T>::MetadataFieldTooLong)?,
+				description: description.try_into().map_err(|_| Error::<T>::MetadataFieldTooLong)?,
+			};
+			ValidatorMetadata::<T>::insert(&who, info);
+			
+			Self::deposit_event(Event::MetadataUpdated(who));
+			
+			Ok(())
+		}
+		
+		/// Withdraw the caller's smallest nominations until it holds no more than the
+		/// nomination limit, after governance has lowered it
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::withdraw_nomination().saturating_mul(T::MaxNominations::get() as u64))]
+		pub fn prune_excess_nominations(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			let mut nominations = Nominators::<T>::get(&who).into_inner();
+			let limit = Self::max_nominations() as usize;
+			ensure!(nominations.len() > limit, Error::<T>::WithinNominationLimit);
+			
+			nominations.sort_by_key(|n| n.amount);
+			let excess = nominations.len() - limit;
+			for nomination in nominations.into_iter().take(excess) {
+				Self::do_withdraw_nomination(who.clone(), nomination.validator, false)?;
+			}
 			
 			Ok(())
 		}
 
 		/// Claim pending rewards
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::do_something())]
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
 		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			
-			// Get pending rewards
-			let rewards = PendingRewards::<T>::get(&who);
-			ensure!(!rewards.is_zero(), Error::<T>::NoRewards);
+			let rewards = Self::claimable_rewards(&who)?;
+			
+			// A deposit below the existential deposit into a dead account would be dropped
+			let recipient = match Payee::<T>::get(&who) {
+				RewardDestination::Account(payee) => payee,
+				_ => who.clone(),
+			};
+			ensure!(
+				!T::Currency::total_balance(&recipient).is_zero() || rewards >= T::Currency::minimum_balance(),
+				Error::<T>::RewardBelowExistential
+			);
 			
-			// Clear pending rewards
-			PendingRewards::<T>::remove(&who);
+			Self::note_claimed(&who);
 			
 			// Transfer rewards
-			T::Currency::deposit_creating(&who, rewards);
+			Self::pay_reward(&who, rewards);
 			
 			// Emit event
 			Self::deposit_event(Event::RewardsClaimed(who, rewards));
@@ -384,154 +1343,1345 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Slash a validator for misbehavior
-		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::do_something())]
-		pub fn slash_validator(
-			origin: OriginFor<T>,
-			validator: T::AccountId,
-			#[pallet::compact] slash_percent: u32,
-		) -> DispatchResult {
-			// Only allow governance or admin to slash
+		/// Set where the caller's claimed rewards are paid
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::set_payee())]
+		pub fn set_payee(origin: OriginFor<T>, payee: RewardDestination<T::AccountId>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			if payee == RewardDestination::Stash {
+				Payee::<T>::remove(&who);
+			} else {
+				Payee::<T>::insert(&who, payee.clone());
+			}
+			
+			Self::deposit_event(Event::PayeeSet(who, payee));
+			
+			Ok(())
+		}
+
+		/// Approve `who` to register as a validator
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::add_to_whitelist())]
+		pub fn add_to_whitelist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
 			ensure_root(origin)?;
 			
-			// Ensure slash percent is valid (1-100%)
-			ensure!(slash_percent > 0 && slash_percent <= 100, Error::<T>::InvalidSlashPercentage);
+			ValidatorWhitelist::<T>::insert(&who, true);
 			
-			// Check if account is a validator
-			let validator_stake = Validators::<T>::get(&validator);
-			ensure!(validator_stake > BalanceOf::<T>::zero(), Error::<T>::NotValidator);
+			Self::deposit_event(Event::AddedToWhitelist(who));
 			
-			// Calculate slash amount
-			let slash_amount = validator_stake
-				.checked_mul(&slash_percent.into())
-				.and_then(|r| r.checked_div(&100u32.into()))
-				.unwrap_or_else(Zero::zero);
+			Ok(())
+		}
+
+		/// Withdraw the approval of `who` to register as a validator. A registered validator
+		/// stays registered
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::remove_from_whitelist())]
+		pub fn remove_from_whitelist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
 			
-			// Ensure slash amount is not zero
-			ensure!(!slash_amount.is_zero(), Error::<T>::ZeroSlashAmount);
+			ValidatorWhitelist::<T>::remove(&who);
 			
-			// Slash the validator's stake
-			Self::do_slash(&validator, slash_amount)?;
+			Self::deposit_event(Event::RemovedFromWhitelist(who));
 			
-			// Emit event
-			Self::deposit_event(Event::ValidatorSlashed(validator, slash_amount, slash_percent));
+			Ok(())
+		}
+
+		/// Immediately end the current era and start the next one.
+		/// A no-op if an era already started in this block.
+		#[pallet::call_index(22)]
+		#[pallet::weight(Pallet::<T>::era_rotation_weight())]
+		pub fn force_new_era(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			
+			let now = frame_system::Pallet::<T>::block_number();
+			if Self::era_start_block() != now {
+				Self::start_new_era(Self::current_era(), now);
+			}
 			
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		// Constants for reward calculation (using integer math)
-		const BLOCKS_PER_YEAR: u32 = 5_256_000; // Assuming 6-second blocks
-		const VALIDATOR_INFLATION_RATE_NUMERATOR: u32 = 15; // 15%
-		const VALIDATOR_INFLATION_RATE_DENOMINATOR: u32 = 100;
-		const NOMINATOR_INFLATION_RATE_NUMERATOR: u32 = 10; // 10%
-		const NOMINATOR_INFLATION_RATE_DENOMINATOR: u32 = 100;
+		/// Claim the commission earned as a validator
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::claim_commission())]
+		pub fn claim_commission(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			ensure!(!MintingPaused::<T>::get(), Error::<T>::MintingPaused);
+			
+			let commission = CommissionEarned::<T>::get(&who);
+			ensure!(!commission.is_zero(), Error::<T>::NoCommission);
+			
+			// A deposit below the existential deposit into a dead account would be dropped
+			ensure!(
+				!T::Currency::total_balance(&who).is_zero() || commission >= T::Currency::minimum_balance(),
+				Error::<T>::RewardBelowExistential
+			);
+			
+			CommissionEarned::<T>::remove(&who);
+			let _ = T::Currency::deposit_creating(&who, commission);
+			
+			Self::deposit_event(Event::CommissionClaimed(who, commission));
+			
+			Ok(())
+		}
 
-		// Era duration in blocks
-    	const ERA_DURATION: u32 = 14_400; // 1 day with 6-second blocks
-		
-		// Distribute rewards to validators and nominators
-		fn distribute_rewards(era: EraIndex) {
-			// Get total staked
-			let total_staked = TotalStaked::<T>::get();
-			if total_staked.is_zero() {
-				return;
+		/// Claim pending rewards and bond them straight into a nomination of `validator`
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::claim_rewards().saturating_add(T::WeightInfo::nominate()))]
+		pub fn claim_and_restake(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			
+			Self::ensure_nominatable(&validator)?;
+			let rewards = Self::claimable_rewards(&who)?;
+			
+			// Top up an existing nomination, or open a new one within the usual limits
+			let mut nominations = Nominators::<T>::get(&who);
+			if let Some(nomination) = nominations.iter_mut().find(|n| n.validator == validator) {
+				nomination.amount = nomination.amount.saturating_add(rewards);
+			} else {
+				ensure!(rewards >= T::MinNomination::get(), Error::<T>::NominationBelowMinimum);
+				ensure!(nominations.len() < Self::max_nominations() as usize, Error::<T>::MaxNominationsReached);
+				nominations
+					.try_push(Nomination { validator: validator.clone(), amount: rewards })
+					.map_err(|_| Error::<T>::MaxNominationsReached)?;
 			}
 			
-			// Calculate total rewards (reward_rate % of total staked)
+			// Mint the rewards and reserve them immediately
+			Self::note_claimed(&who);
+			let _ = T::Currency::deposit_creating(&who, rewards);
+			T::Currency::reserve(&who, rewards)?;
+			
+			Self::put_nominations(&who, nominations)?;
+			Self::note_bonded(&who);
+			
+			// Update total validator stake
+			TotalValidatorStake::<T>::mutate(&validator, |total| {
+				*total = total.checked_add(&rewards).unwrap_or(*total);
+			});
+			
+			// Update total staked
+			let old_total = TotalStaked::<T>::get();
+			let new_total = old_total.checked_add(&rewards).unwrap_or(old_total);
+			TotalStaked::<T>::put(new_total);
+			
+			Self::deposit_event(Event::RewardsClaimed(who.clone(), rewards));
+			Self::deposit_event(Event::Nomination(who, validator, rewards));
+			
+			Ok(())
+		}
+
+		/// Slash a validator for misbehavior in `era`, against its self stake in that era.
+		/// Nominators are only slashed for `SlashCategory::Malice`
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::slash_validator(T::MaxNominatorsPerValidator::get()))]
+		pub fn slash_validator(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			#[pallet::compact] slash_percent: u32,
+			era: EraIndex,
+			category: SlashCategory,
+		) -> DispatchResult {
+			// Only allow the configured slashing authority
+			T::SlashOrigin::ensure_origin(origin)?;
+			
+			// Ensure slash percent is valid (1-100%)
+			ensure!(slash_percent > 0 && slash_percent <= 100, Error::<T>::InvalidSlashPercentage);
+			
+			Self::slash_by_percent(&validator, slash_percent, era, category)?;
+			
+			Ok(())
+		}
+
+		/// Slash several validators at once for malice as `(validator, percent, offence era)`,
+		/// skipping entries that cannot be slashed
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::slash_validator(T::MaxNominatorsPerValidator::get())
+			.saturating_mul(slashes.len() as u64))]
+		pub fn slash_validators(
+			origin: OriginFor<T>,
+			slashes: Vec<(T::AccountId, u32, EraIndex)>,
+		) -> DispatchResult {
+			T::SlashOrigin::ensure_origin(origin)?;
+			
+			ensure!(slashes.len() <= T::MaxBatchSlashes::get() as usize, Error::<T>::TooManySlashes);
+			
+			// A bad percentage is an input error, so reject the whole batch
+			ensure!(
+				slashes.iter().all(|(_, percent, _)| *percent > 0 && *percent <= 100),
+				Error::<T>::InvalidSlashPercentage
+			);
+			
+			let mut slashed_count: u32 = 0;
+			let mut total_slashed = BalanceOf::<T>::zero();
+			
+			for (validator, slash_percent, era) in slashes.iter() {
+				// Entries that cannot be slashed (e.g. not a validator) are skipped
+				if let Ok(amount) = Self::slash_by_percent(validator, *slash_percent, *era, SlashCategory::Malice) {
+					slashed_count += 1;
+					total_slashed = total_slashed.saturating_add(amount);
+				}
+			}
+			
+			Self::deposit_event(Event::ValidatorsSlashed(slashed_count, total_slashed));
+			
+			Ok(())
+		}
+
+		/// Remove up to `limit` validators whose self stake is below the current minimum,
+		/// returning their stake
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::remove_validator(T::MaxNominatorsPerValidator::get())
+			.saturating_mul(*limit as u64)
+			.saturating_add(T::DbWeight::get().reads(T::MaxValidators::get() as u64)))]
+		pub fn prune_below_minimum(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			ensure_root(origin)?;
+			
+			let min_stake = T::MinStake::get();
+			let below_minimum: Vec<(T::AccountId, BalanceOf<T>)> = Validators::<T>::iter()
+				.filter(|(_, stake)| *stake < min_stake)
+				.take(limit as usize)
+				.collect();
+			
+			for (validator, stake) in below_minimum {
+				// Validators whose stake cannot start unbonding are left for a later prune
+				if Self::do_remove_validator(&validator, stake).is_ok() {
+					Self::deposit_event(Event::ValidatorDeactivated(validator, stake));
+				}
+			}
+			
+			Ok(())
+		}
+		
+		/// Lower the number of nominations an account may hold below `MaxNominations`, or
+		/// restore it. Accounts over the new limit cannot nominate again until they prune
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::set_nomination_limit())]
+		pub fn set_nomination_limit(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			ensure_root(origin)?;
+			
+			ensure!(limit <= T::MaxNominations::get(), Error::<T>::NominationLimitTooHigh);
+			NominationLimit::<T>::put(limit);
+			
+			Self::deposit_event(Event::NominationLimitSet(limit));
+			
+			Ok(())
+		}
+		
+		/// Pause or resume reward minting. Rewards keep accruing while paused.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::set_minting_paused())]
+		pub fn set_minting_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			ensure_root(origin)?;
+			
+			MintingPaused::<T>::put(paused);
+			
+			Self::deposit_event(Event::MintingPausedSet(paused));
+			
+			// The treasury cut held back while paused is minted on resuming
+			let held_cut = if paused { Zero::zero() } else { PendingTreasuryCut::<T>::take() };
+			if !held_cut.is_zero() {
+				let _ = T::Currency::deposit_creating(&T::TreasuryAccount::get(), held_cut);
+				Self::deposit_event(Event::TreasuryFunded(Self::current_era(), held_cut));
+			}
+			
+			Ok(())
+		}
+		
+		/// Open a misbehavior report against a validator, holding nominator withdrawals
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::report_misbehavior())]
+		pub fn report_misbehavior(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+			
+			ensure!(Validators::<T>::contains_key(&validator), Error::<T>::NotValidator);
+			ensure!(!UnderInvestigation::<T>::get(&validator), Error::<T>::AlreadyUnderInvestigation);
+			
+			UnderInvestigation::<T>::insert(&validator, true);
+			
+			Self::deposit_event(Event::MisbehaviorReported(validator));
+			
+			Ok(())
+		}
+		
+		/// Close a validator's misbehavior report and release held withdrawals
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::resolve_report(T::MaxNominatorsPerValidator::get()))]
+		pub fn resolve_report(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+			
+			ensure!(UnderInvestigation::<T>::get(&validator), Error::<T>::NotUnderInvestigation);
+			
+			UnderInvestigation::<T>::remove(&validator);
+			
+			// Release held withdrawals, minus anything slashed in the meantime
+			for (nominator, amount) in HeldWithdrawals::<T>::drain_prefix(&validator) {
+				let Ok(freed) = Self::return_funds(&nominator, amount) else {
+					// A nominator the release would reap unbonds as usual instead, whatever its
+					// unbonding chunks hold
+					let held = T::Currency::reserved_balance(&nominator).min(amount);
+					Self::force_unbond(&nominator, held);
+					continue;
+				};
+				Self::deposit_event(Event::NominationWithdrawn(nominator, validator.clone(), freed));
+			}
+			
+			Self::deposit_event(Event::ReportResolved(validator));
+			
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		// Constant for reward calculation (using integer math)
+		const BLOCKS_PER_YEAR: u32 = 5_256_000; // Assuming 6-second blocks
+		
+		// Weight of an era rotation, whether `on_initialize` or `force_new_era` starts it. The
+		// rotation visits every validator and every nomination, which is bounded both by the
+		// validators' backer limit and by what the nominators can hold.
+		fn era_rotation_weight() -> Weight {
+			let validators = ValidatorCount::<T>::get();
+			let nominations = validators
+				.saturating_mul(T::MaxNominatorsPerValidator::get())
+				.min(NominatorCount::<T>::get().saturating_mul(T::MaxNominations::get()));
+			T::WeightInfo::new_era(validators, nominations)
+		}
+		
+		// Rotate into the era after `current_era`, paying out the era that just ended
+		fn start_new_era(current_era: EraIndex, n: BlockNumberFor<T>) {
+			CurrentEra::<T>::put(current_era + 1);
+			EraStartBlock::<T>::put(n);
+			NewValidatorsThisEra::<T>::kill();
+			Self::deposit_event(Event::NewEra(current_era + 1, n));
+			
+			// Distribute rewards for the previous era
+			Self::distribute_rewards(current_era);
+			Self::prune_reward_history(current_era);
+			Self::chill_zero_stake_validators();
+			
+			// Elect the active set for the new era
+			Self::elect_active_set();
+			
+			// Record the stakes offences in the new era are slashed against
+			Self::snapshot_stakes(current_era + 1);
+		}
+		
+		// Distribute rewards to validators and nominators
+		fn distribute_rewards(era: EraIndex) {
+			// Nothing is paid before the configured start era
+			if era < T::RewardStartEra::get() {
+				Self::deposit_event(Event::RewardsNotYetStarted(era));
+				return;
+			}
+			
+			let Some(distribution) = Self::era_distribution(era) else {
+				return;
+			};
+			
+			// The treasury is paid its cut and the rounding residual before stakers. While
+			// minting is paused this accrues like stakers' rewards do
+			let to_treasury = distribution.treasury.saturating_add(distribution.residual);
+			if MintingPaused::<T>::get() {
+				PendingTreasuryCut::<T>::mutate(|held| *held = held.saturating_add(to_treasury));
+			} else if !to_treasury.is_zero() {
+				let _ = T::Currency::deposit_creating(&T::TreasuryAccount::get(), to_treasury);
+				Self::deposit_event(Event::TreasuryFunded(era, to_treasury));
+			}
+			if !distribution.residual.is_zero() {
+				Self::deposit_event(Event::RewardResidual(era, distribution.residual));
+			}
+			
+			// Add to earned commission and pending rewards, minted when claimed
+			let mut distributed = BalanceOf::<T>::zero();
+			for (validator, commission) in distribution.commissions {
+				if !commission.is_zero() {
+					CommissionEarned::<T>::mutate(&validator, |earned| {
+						*earned = earned.saturating_add(commission);
+					});
+					EraRewards::<T>::mutate(era, &validator, |earned| *earned = earned.saturating_add(commission));
+					distributed = distributed.saturating_add(commission);
+				}
+			}
+			for (account, reward) in distribution.rewards {
+				if !reward.is_zero() {
+					Self::accrue_reward(&account, reward);
+					EraRewards::<T>::mutate(era, &account, |earned| *earned = earned.saturating_add(reward));
+					distributed = distributed.saturating_add(reward);
+				}
+			}
+			// The treasury's share counts towards the pool's growth
+			let minted = distributed.saturating_add(to_treasury);
+			PrevEraPool::<T>::put(minted);
+			EraTotalRewards::<T>::insert(era, distributed);
+			
+			// Emit event with total rewards for the era
+			Self::deposit_event(Event::RewardsDistributed(era, distributed));
+		}
+
+		// Treasury cut, commission and rewards distributed for `era` given the current state,
+		// already scaled to the clamped pool. `None` when nothing is distributed. Reads storage only.
+		fn era_distribution(era: EraIndex) -> Option<EraDistribution<T>> {
+			if era < T::RewardStartEra::get() {
+				return None;
+			}
+			
+			// Get total staked
+			let total_staked = TotalStaked::<T>::get();
+			if total_staked.is_zero() {
+				return None;
+			}
+			
+			// Calculate total rewards (reward_rate % of total staked)
 			let reward_rate = T::RewardRate::get();
 			if reward_rate == 0 {
-				return;
+				return None;
 			}
 			
-			// Create rewards for validators and nominators
+			// Compute each validator's commission and each validator's and nominator's reward
+			// for the era
+			let mut commissions: PayoutsOf<T> = Vec::new();
+			let mut rewards: PayoutsOf<T> = Vec::new();
+			let mut unassigned = BalanceOf::<T>::zero();
 			for (validator, validator_stake) in Validators::<T>::iter() {
-				// Get total stake for this validator
-				let total_validator_stake = TotalValidatorStake::<T>::get(&validator);
-				if total_validator_stake.is_zero() {
+				if !Self::is_rewardable(&validator, era) {
 					continue;
 				}
 				
-				// Calculate validator's reward
-				let validator_reward = validator_stake
-					.checked_mul(&Self::VALIDATOR_INFLATION_RATE_NUMERATOR.into())
-					.and_then(|r| r.checked_div(&(Self::BLOCKS_PER_YEAR * Self::VALIDATOR_INFLATION_RATE_DENOMINATOR).into()))
-					.unwrap_or_else(Zero::zero)
-					.checked_mul(&Self::ERA_DURATION.into())
-					.unwrap_or_else(Zero::zero);
+				let (commission, era_payouts, dust) = Self::era_payouts(&validator, validator_stake, era);
+				commissions.push((validator, commission));
+				rewards.extend(era_payouts);
+				unassigned = unassigned.saturating_add(dust);
+			}
+			
+			let sum = |payouts: &PayoutsOf<T>| {
+				payouts.iter().fold(BalanceOf::<T>::zero(), |sum, (_, reward)| sum.saturating_add(*reward))
+			};
+			let pool = sum(&commissions).saturating_add(sum(&rewards)).saturating_add(unassigned);
+			
+			// Clamp the pool's growth over the previous era, scaling every reward down alike
+			let prev_pool = PrevEraPool::<T>::get();
+			let max_pool = prev_pool
+				.saturating_add(Perbill::from_percent(T::MaxRewardGrowth::get()).mul_floor(prev_pool));
+			let clamped_to = (!prev_pool.is_zero() && pool > max_pool).then_some(max_pool);
+			let minted = clamped_to.unwrap_or(pool);
+			
+			// The treasury takes its cut of the minted pool and stakers split the rest
+			let cut = T::TreasuryCut::get();
+			let treasury = cut.mul_floor(minted);
+			let scale = clamped_to.map_or(Perbill::one(), |max_pool| Perbill::from_rational(max_pool, pool))
+				* cut.left_from_one();
+			if !scale.is_one() {
+				let scale_all = |payouts: PayoutsOf<T>| -> PayoutsOf<T> {
+					payouts.into_iter().map(|(account, amount)| (account, scale.mul_floor(amount))).collect()
+				};
+				commissions = scale_all(commissions);
+				rewards = scale_all(rewards);
+			}
+			
+			// Rounding down every share leaves part of the minted pool unpaid
+			let residual = minted
+				.saturating_sub(treasury)
+				.saturating_sub(sum(&commissions))
+				.saturating_sub(sum(&rewards));
+			Some(EraDistribution { treasury, commissions, rewards, residual })
+		}
+
+		/// What the next reward distribution would credit to each account, commission
+		/// included, given the current state. Nothing is written.
+		pub fn simulate_era_rewards() -> Vec<(T::AccountId, BalanceOf<T>)> {
+			let Some(distribution) = Self::era_distribution(Self::current_era()) else {
+				return Vec::new();
+			};
+			
+			let mut credited: PayoutsOf<T> = Vec::new();
+			for (account, amount) in distribution.commissions.into_iter().chain(distribution.rewards) {
+				if amount.is_zero() {
+					continue;
+				}
+				match credited.iter_mut().find(|(credited_account, _)| *credited_account == account) {
+					Some((_, total)) => *total = total.saturating_add(amount),
+					None => credited.push((account, amount)),
+				}
+			}
+			credited
+		}
+
+		// Drop the reward history of the era that falls out of `HistoryDepth` once `era` has
+		// been distributed
+		fn prune_reward_history(era: EraIndex) {
+			let Some(expired) = era.checked_sub(T::HistoryDepth::get()) else {
+				return;
+			};
+			let _ = EraRewards::<T>::clear_prefix(expired, u32::MAX, None);
+			EraTotalRewards::<T>::remove(expired);
+		}
+
+		// Count the eras each validator ends with zero total stake and chill the ones that
+		// reach `MaxZeroStakeEras`
+		fn chill_zero_stake_validators() {
+			for validator in Validators::<T>::iter_keys() {
+				if Chilled::<T>::get(&validator) {
+					continue;
+				}
+				if !TotalValidatorStake::<T>::get(&validator).is_zero() {
+					ZeroStakeEras::<T>::remove(&validator);
+					continue;
+				}
 				
-				if !validator_reward.is_zero() {
-					// Add to validator's pending rewards
-					PendingRewards::<T>::mutate(&validator, |rewards| {
-						*rewards = rewards.checked_add(&validator_reward).unwrap_or(*rewards);
-					});
+				let eras = ZeroStakeEras::<T>::get(&validator).saturating_add(1);
+				if eras >= T::MaxZeroStakeEras::get() {
+					ZeroStakeEras::<T>::remove(&validator);
+					Chilled::<T>::insert(&validator, true);
+					Self::deposit_event(Event::ValidatorChilled(validator));
+				} else {
+					ZeroStakeEras::<T>::insert(&validator, eras);
+				}
+			}
+		}
+
+		// Whether `validator` and its nominators earn rewards for `era`
+		fn is_rewardable(validator: &T::AccountId, era: EraIndex) -> bool {
+			Validators::<T>::contains_key(validator)
+				&& !Chilled::<T>::get(validator)
+				&& Validators::<T>::get(validator) >= T::MinStake::get()
+				&& !TotalValidatorStake::<T>::get(validator).is_zero()
+				&& Self::backer_count(validator) >= T::MinNominators::get()
+				&& (!T::RewardActiveSetOnly::get() || ActiveSet::<T>::get().contains(validator))
+				&& Self::has_completed_proving(validator, era)
+		}
+
+		// Whether `validator` has spent `MinProvingEras` in the active set by `era`
+		fn has_completed_proving(validator: &T::AccountId, era: EraIndex) -> bool {
+			let proving_eras = T::MinProvingEras::get();
+			proving_eras == 0
+				|| FirstActiveEra::<T>::get(validator)
+					.is_some_and(|first| era >= first.saturating_add(proving_eras))
+		}
+
+		// Elect the validators with the highest total stake as the active set
+		fn elect_active_set() {
+			let mut candidates: Vec<(T::AccountId, BalanceOf<T>)> = Validators::<T>::iter_keys()
+				.filter(|validator| !Chilled::<T>::get(validator))
+				.map(|validator| {
+					let stake = TotalValidatorStake::<T>::get(&validator);
+					(validator, stake)
+				})
+				.collect();
+			// Highest stake first, ties broken by account for a deterministic result
+			candidates.sort_by(|(a, a_stake), (b, b_stake)| b_stake.cmp(a_stake).then_with(|| a.cmp(b)));
+			
+			let elected: Vec<T::AccountId> = candidates
+				.into_iter()
+				.take(T::MaxActiveValidators::get() as usize)
+				.map(|(validator, _)| validator)
+				.collect();
+			
+			// Newly elected validators start their proving period
+			let era = Self::current_era();
+			for validator in elected.iter() {
+				if !FirstActiveEra::<T>::contains_key(validator) {
+					FirstActiveEra::<T>::insert(validator, era);
+				}
+			}
+			ActiveSet::<T>::put(BoundedVec::truncate_from(elected));
+		}
+
+		// Era commission of a validator and rewards of the validator and its nominators. The
+		// pool is earned by the validator's total stake; the validator takes its commission off
+		// the top and each nominator gets the rest pro rata to its nomination. Whatever is not
+		// paid to nominators, rounding dust included, goes to the validator as reward. Also
+		// returns the dust left unpaid when splitting the redirected commission.
+		fn era_payouts(
+			validator: &T::AccountId,
+			validator_stake: BalanceOf<T>,
+			era: EraIndex,
+		) -> (BalanceOf<T>, PayoutsOf<T>, BalanceOf<T>) {
+			let total_stake = TotalValidatorStake::<T>::get(validator).max(validator_stake);
+			let pool = Self::validator_era_reward(validator_stake)
+				.saturating_add(Self::nominator_era_reward(total_stake.saturating_sub(validator_stake)));
+			if pool.is_zero() {
+				return (Zero::zero(), Vec::new(), Zero::zero());
+			}
+			
+			let rate = ValidatorCommission::<T>::get(validator);
+			let commission = rate.min(T::SoftCommissionCap::get()).mul_floor(pool);
+			let excess = rate.mul_floor(pool).saturating_sub(commission);
+			let remainder = pool.saturating_sub(commission).saturating_sub(excess);
+			
+			// Commission above the soft cap is split among the nominators by nomination
+			let redirected = if T::RedirectExcessCommission::get() { excess } else { Zero::zero() };
+			let nominated = total_stake.saturating_sub(validator_stake);
+			
+			let mut payouts = alloc::vec![(validator.clone(), Zero::zero())];
+			let mut paid: BalanceOf<T> = Zero::zero();
+			let mut paid_extra: BalanceOf<T> = Zero::zero();
+			for nominator in NominatorsByValidator::<T>::get(validator) {
+				// A validator never earns a second time as its own nominator
+				if nominator == *validator {
+					continue;
+				}
+				let Some(nomination) = Nominators::<T>::get(&nominator)
+					.into_iter()
+					.find(|n| n.validator == *validator)
+				else {
+					continue;
+				};
+				
+				let share = Perbill::from_rational(nomination.amount, total_stake).mul_floor(remainder);
+				paid = paid.saturating_add(share);
+				let extra = Perbill::from_rational(nomination.amount, nominated).mul_floor(redirected);
+				paid_extra = paid_extra.saturating_add(extra);
+				payouts.push((nominator, share.saturating_add(extra)));
+			}
+			payouts[0].1 = remainder.saturating_sub(paid);
+			
+			// Stakers bonded without a withdrawal earn their loyalty bonus on top
+			for (account, payout) in payouts.iter_mut() {
+				*payout = payout.saturating_add(Self::loyalty_bonus(account, era).mul_floor(*payout));
+			}
+			(commission, payouts, redirected.saturating_sub(paid_extra))
+		}
+
+		// Reward bonus of `who` for `era`, growing linearly with the eras bonded without a
+		// withdrawal until it reaches `MaxLoyaltyBonus` after `LoyaltyRampEras`
+		fn loyalty_bonus(who: &T::AccountId, era: EraIndex) -> Perbill {
+			let Some(since) = BondedSinceEra::<T>::get(who) else {
+				return Perbill::zero();
+			};
+			let ramp = T::LoyaltyRampEras::get();
+			if ramp == 0 {
+				return T::MaxLoyaltyBonus::get();
+			}
+			Perbill::from_rational(era.saturating_sub(since).min(ramp), ramp) * T::MaxLoyaltyBonus::get()
+		}
+
+		// Start the continuous bonding period of `who` unless one is already running
+		fn note_bonded(who: &T::AccountId) {
+			if !BondedSinceEra::<T>::contains_key(who) {
+				BondedSinceEra::<T>::insert(who, Self::current_era());
+			}
+		}
+
+		// Restart the continuous bonding period of `who` after a withdrawal, or end it when
+		// nothing is left bonded
+		fn reset_bonded_since(who: &T::AccountId) {
+			if Validators::<T>::contains_key(who) || Nominators::<T>::contains_key(who) {
+				BondedSinceEra::<T>::insert(who, Self::current_era());
+			} else {
+				BondedSinceEra::<T>::remove(who);
+			}
+		}
+
+		// Add `reward` to the pending rewards of `who`. If that would exceed `MaxPendingReward`,
+		// the rewards pending so far are paid out first as a forced claim.
+		fn accrue_reward(who: &T::AccountId, reward: BalanceOf<T>) {
+			let pending = PendingRewards::<T>::get(who);
+			
+			// No forced payout while minting is paused, the reward just accrues
+			if !pending.is_zero()
+				&& pending.saturating_add(reward) > T::MaxPendingReward::get()
+				&& !MintingPaused::<T>::get()
+			{
+				Self::pay_reward(who, pending);
+				Self::deposit_event(Event::RewardsClaimed(who.clone(), pending));
+				PendingRewards::<T>::insert(who, reward);
+			} else {
+				PendingRewards::<T>::insert(who, pending.saturating_add(reward));
+			}
+		}
+
+		// Mint claimed `rewards` of `who` to its reward destination. Staked rewards go to the
+		// validator self stake, or else the first nomination, and stay free if neither exists.
+		fn pay_reward(who: &T::AccountId, rewards: BalanceOf<T>) {
+			match Payee::<T>::get(who) {
+				RewardDestination::Stash => {
+					let _ = T::Currency::deposit_creating(who, rewards);
+				},
+				RewardDestination::Account(payee) => {
+					let _ = T::Currency::deposit_creating(&payee, rewards);
+				},
+				RewardDestination::Staked => {
+					let _ = T::Currency::deposit_creating(who, rewards);
+					Self::bond_reward(who, rewards);
+				},
+			}
+		}
+
+		// Bond minted `rewards` into the stake of `who`
+		fn bond_reward(who: &T::AccountId, rewards: BalanceOf<T>) {
+			let mut nominations = Nominators::<T>::get(who);
+			let validator = if Validators::<T>::contains_key(who) {
+				who.clone()
+			} else if let Some(nomination) = nominations.iter_mut().next() {
+				nomination.amount = nomination.amount.saturating_add(rewards);
+				nomination.validator.clone()
+			} else {
+				return;
+			};
+			if T::Currency::reserve(who, rewards).is_err() {
+				return;
+			}
+			
+			if validator == *who {
+				let self_stake = Validators::<T>::mutate(who, |stake| {
+					*stake = stake.saturating_add(rewards);
+					*stake
+				});
+				Self::deposit_event(Event::ValidatorBondedExtra(who.clone(), rewards));
+				Self::deposit_event(Event::ValidatorStakeIncreased(who.clone(), self_stake));
+			} else {
+				Nominators::<T>::insert(who, nominations);
+				Self::deposit_event(Event::Nomination(who.clone(), validator.clone(), rewards));
+			}
+			TotalValidatorStake::<T>::mutate(&validator, |total| *total = total.saturating_add(rewards));
+			TotalStaked::<T>::mutate(|total| *total = total.saturating_add(rewards));
+		}
+
+		// Reward earned over one era by a validator's self stake
+		fn validator_era_reward(stake: BalanceOf<T>) -> BalanceOf<T> {
+			Self::era_inflation(T::ValidatorInflation::get(), stake)
+		}
+
+		// Reward earned over one era by a single nomination
+		fn nominator_era_reward(amount: BalanceOf<T>) -> BalanceOf<T> {
+			Self::era_inflation(T::NominatorInflation::get(), amount)
+		}
+
+		// Share of the annual `rate` on `amount` earned over one era
+		fn era_inflation(rate: Perbill, amount: BalanceOf<T>) -> BalanceOf<T> {
+			let era_duration: u32 = T::EraDuration::get().unique_saturated_into();
+			(rate.mul_floor(amount) / Self::BLOCKS_PER_YEAR.into())
+				.checked_mul(&era_duration.into())
+				.unwrap_or_else(Zero::zero)
+		}
+
+		/// Status of `validator` as reported to its nominators
+		pub fn validator_status(validator: &T::AccountId) -> ValidatorStatus {
+			if Validators::<T>::contains_key(validator) {
+				ValidatorStatus::Active
+			} else {
+				ValidatorStatus::NotValidator
+			}
+		}
+
+		/// Total amount `who` has bonded across all of its nominations
+		pub fn total_nominated(who: &T::AccountId) -> BalanceOf<T> {
+			Nominators::<T>::get(who)
+				.iter()
+				.fold(Zero::zero(), |total: BalanceOf<T>, n| total.saturating_add(n.amount))
+		}
+
+		/// Number of nominations an account may hold
+		pub fn max_nominations() -> u32 {
+			NominationLimit::<T>::get().unwrap_or_else(T::MaxNominations::get).min(T::MaxNominations::get())
+		}
+
+		/// Whether `who` has pending rewards or unbonded stake ready to withdraw
+		pub fn has_claimable(who: &T::AccountId) -> bool {
+			let current_era = Self::current_era();
+			!PendingRewards::<T>::get(who).is_zero()
+				|| Unbonding::<T>::get(who).iter().any(|(_, unlock_era)| *unlock_era <= current_era)
+		}
+
+		/// First era whose offences `validator` can be slashed for, once its registration
+		/// grace period is over. `None` if it is not a validator.
+		pub fn slashable_from_era(validator: &T::AccountId) -> Option<EraIndex> {
+			Validators::<T>::contains_key(validator)
+				.then(|| RegisteredAtEra::<T>::get(validator).saturating_add(T::SlashGracePeriod::get()))
+		}
+
+		/// Number of nominators currently backing `validator`
+		pub fn backer_count(validator: &T::AccountId) -> u32 {
+			NominatorsByValidator::<T>::decode_len(validator).unwrap_or(0) as u32
+		}
+
+		/// Reward `who` would be credited at the next era rollover given current stake,
+		/// after the growth clamp and the treasury cut. Commission is not included.
+		pub fn projected_era_reward(who: &T::AccountId) -> BalanceOf<T> {
+			let Some(distribution) = Self::era_distribution(Self::current_era()) else {
+				return Zero::zero();
+			};
+
+			distribution
+				.rewards
+				.into_iter()
+				.filter(|(account, _)| account == who)
+				.fold(BalanceOf::<T>::zero(), |sum, (_, reward)| sum.saturating_add(reward))
+		}
+
+		/// Share of the total stake behind `validator` that is its own stake. Zero for accounts
+		/// that are not validators or have no stake.
+		pub fn self_stake_ratio(validator: &T::AccountId) -> Perbill {
+			let self_stake = Validators::<T>::get(validator);
+			let total_stake = TotalValidatorStake::<T>::get(validator).max(self_stake);
+			if total_stake.is_zero() {
+				return Perbill::zero();
+			}
+			Perbill::from_rational(self_stake, total_stake)
+		}
+
+		// Whether `validator` holds less of its total stake than `MinSelfStakeRatio` requires
+		fn below_min_self_stake_ratio(validator: &T::AccountId) -> bool {
+			let min_ratio = T::MinSelfStakeRatio::get();
+			!min_ratio.is_zero() && Self::self_stake_ratio(validator) < min_ratio
+		}
+
+		/// Annual return a nominator of `validator` earns after the treasury cut and the
+		/// validator's commission. The pool is shared pro rata by stake, so the rate blends the
+		/// validator and nominator inflation rates by how much of the total stake is self stake; a
+		/// validator without nominations offers the validator rate. Zero for accounts that are not
+		/// earning rewards.
+		pub fn validator_effective_apr(validator: &T::AccountId) -> Perbill {
+			if !Self::is_rewardable(validator, Self::current_era()) {
+				return Perbill::zero();
+			}
+			
+			let self_stake = Validators::<T>::get(validator);
+			let total_stake = TotalValidatorStake::<T>::get(validator).max(self_stake);
+			let nominated = total_stake.saturating_sub(self_stake);
+			
+			let weighted = T::ValidatorInflation::get()
+				.mul_floor(self_stake)
+				.saturating_add(T::NominatorInflation::get().mul_floor(nominated));
+			let pool_apr = Perbill::from_rational(weighted, total_stake);
+			// Commission redirected past the soft cap stays with the nominators
+			let mut commission = ValidatorCommission::<T>::get(validator);
+			if T::RedirectExcessCommission::get() {
+				commission = commission.min(T::SoftCommissionCap::get());
+			}
+			commission.left_from_one() * T::TreasuryCut::get().left_from_one() * pool_apr
+		}
+
+		/// Aggregated nominator view: bonded total, per-validator nominations, pending and
+		/// projected rewards. Returns zeroed fields for an account that does not nominate.
+		pub fn nominator_dashboard(who: &T::AccountId) -> NominatorDashboard<T::AccountId, BalanceOf<T>> {
+			let nominations = Nominators::<T>::get(who)
+				.into_iter()
+				.map(|n| {
+					let status = Self::validator_status(&n.validator);
+					(n.validator, n.amount, status)
+				})
+				.collect();
+
+			NominatorDashboard {
+				total_bonded: Self::total_nominated(who),
+				nominations,
+				pending_rewards: PendingRewards::<T>::get(who),
+				estimated_next_era_reward: Self::projected_era_reward(who),
+				last_claimed_era: LastClaimedEra::<T>::get(who),
+			}
+		}
+
+		// Return at most `amount` of reserved stake to `who`, capped by what is still reserved,
+		// and return the amount actually freed. Stake that would leave the account below the
+		// existential deposit is handled per `DustHandling` instead of being reaped.
+		fn return_funds(who: &T::AccountId, amount: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+			let total_reserved = T::Currency::reserved_balance(who);
+			let reserved = total_reserved.min(amount);
+			let free = T::Currency::free_balance(who);
+			let would_be_dust = !reserved.is_zero()
+				&& reserved == total_reserved
+				&& free.saturating_add(reserved) < T::Currency::minimum_balance();
+			
+			if !would_be_dust {
+				let remaining = T::Currency::unreserve(who, reserved);
+				return Ok(reserved.saturating_sub(remaining));
+			}
+			
+			match T::DustHandling::get() {
+				DustPolicy::Collect(collector) => {
+					// Free dust first, so the account is only reaped once nothing is left
+					let mut dust = T::Currency::withdraw(
+						who,
+						free,
+						WithdrawReasons::TRANSFER,
+						ExistenceRequirement::AllowDeath,
+					).unwrap_or_else(|_| NegativeImbalanceOf::<T>::zero());
+					dust.subsume(T::Currency::slash_reserved(who, reserved).0);
+					let collected = dust.peek();
+					T::Currency::resolve_creating(&collector, dust);
 					
-					// Create the reward tokens
-					let _ = T::Currency::deposit_creating(&validator, validator_reward);
+					Self::deposit_event(Event::DustCollected(who.clone(), collector, collected));
+					Ok(Zero::zero())
+				},
+				DustPolicy::KeepAlive => Err(Error::<T>::WithdrawalWouldReap.into()),
+			}
+		}
+
+		// Slash `slash_percent` of a validator's self stake in the offence `era` and emit
+		// `ValidatorSlashed`, returning the amount slashed
+		fn slash_by_percent(
+			validator: &T::AccountId,
+			slash_percent: u32,
+			era: EraIndex,
+			category: SlashCategory,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			// A validator that left after the offence stays slashable through its snapshot for
+			// the offence era, its stake being still unbonding
+			let current_stake = Validators::<T>::get(validator);
+			let snapshot = EraStakeSnapshot::<T>::get(era, validator);
+			ensure!(current_stake > BalanceOf::<T>::zero() || snapshot.is_some(), Error::<T>::NotValidator);
+			
+			// Stake unbonded since the offence is still reserved within the unbonding period
+			let current_era = Self::current_era();
+			ensure!(
+				era <= current_era && current_era <= era.saturating_add(T::UnbondingPeriod::get()),
+				Error::<T>::InvalidOffenceEra
+			);
+			// A removed validator's registration era is kept while its snapshots last
+			let slashable_from = RegisteredAtEra::<T>::get(validator).saturating_add(T::SlashGracePeriod::get());
+			ensure!(era >= slashable_from, Error::<T>::InSlashGracePeriod);
+			let validator_stake = snapshot.unwrap_or(current_stake);
+			
+			// Calculate slash amount
+			let slash_amount = validator_stake
+				.checked_mul(&slash_percent.into())
+				.and_then(|r| r.checked_div(&100u32.into()))
+				.unwrap_or_else(Zero::zero);
+			
+			// Ensure slash amount is not zero
+			ensure!(!slash_amount.is_zero(), Error::<T>::ZeroSlashAmount);
+			
+			// Slash the validator's stake
+			Self::do_slash(validator, slash_amount, slash_percent, category)?;
+			
+			Self::deposit_event(Event::ValidatorSlashed(validator.clone(), slash_amount, slash_percent, category));
+			Self::record_offence(validator, era, slash_percent);
+			
+			// Let the validator's nominators know their validator was slashed
+			let nominator_count = Self::backer_count(validator);
+			if nominator_count > 0 {
+				Self::deposit_event(Event::ValidatorSlashedWithBackers(
+					validator.clone(),
+					slash_amount,
+					nominator_count,
+				));
+			}
+			
+			Ok(slash_amount)
+		}
+
+		// Add an offence to the history of `validator`, dropping the oldest when it is full
+		fn record_offence(validator: &T::AccountId, era: EraIndex, slash_percent: u32) {
+			OffenceHistory::<T>::mutate(validator, |history| {
+				if history.is_full() && !history.is_empty() {
+					history.remove(0);
 				}
+				let _ = history.try_push((era, slash_percent));
+			});
+			OffenceCount::<T>::mutate(validator, |count| *count = count.saturating_add(1));
+		}
+
+		// Record the self stake of every validator for `era` and drop the snapshots of the era
+		// that can no longer be slashed
+		fn snapshot_stakes(era: EraIndex) {
+			for (validator, stake) in Validators::<T>::iter() {
+				EraStakeSnapshot::<T>::insert(era, validator, stake);
+			}
+			if let Some(expired) = era.checked_sub(T::UnbondingPeriod::get().saturating_add(1)) {
+				for (validator, _) in EraStakeSnapshot::<T>::drain_prefix(expired) {
+					// A removed validator is no longer slashable once its last snapshot is gone
+					if !Validators::<T>::contains_key(&validator)
+						&& !EraStakeSnapshot::<T>::contains_key(expired + 1, &validator)
+					{
+						RegisteredAtEra::<T>::remove(&validator);
+					}
+				}
+			}
+		}
+
+		// Record the self stake of `who` for the current era before it is reduced, unless the
+		// era already has a snapshot
+		fn note_stake_snapshot(who: &T::AccountId) {
+			let era = Self::current_era();
+			if !EraStakeSnapshot::<T>::contains_key(era, who) {
+				EraStakeSnapshot::<T>::insert(era, who, Validators::<T>::get(who));
+			}
+		}
+
+		// Drop a validator and return its self stake
+		fn do_remove_validator(who: &T::AccountId, stake: BalanceOf<T>) -> DispatchResult {
+			Self::note_stake_snapshot(who);
+			
+			// Start unbonding the stake
+			Self::schedule_unbond(who, stake)?;
+			
+			// Nominations leave with the validator, so a later registration starts from its
+			// own stake alone. No backer's unbonding state may block the removal.
+			for nominator in NominatorsByValidator::<T>::get(who) {
+				Self::do_withdraw_nomination(nominator, who.clone(), true)?;
+			}
+			
+			// Remove validator
+			Self::cleanup_validator(who);
+			Self::reset_bonded_since(who);
+			
+			// Decrement validator count
+			ValidatorCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+			
+			// Update total staked
+			let old_total = TotalStaked::<T>::get();
+			let new_total = old_total.checked_sub(&stake).unwrap_or(old_total);
+			TotalStaked::<T>::put(new_total);
+			
+			Ok(())
+		}
+
+		// Pending rewards of `who`, once the checks every claim makes pass: minting is not
+		// paused, something is pending and `MinClaimInterval` has passed since the last claim
+		fn claimable_rewards(who: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+			ensure!(!MintingPaused::<T>::get(), Error::<T>::MintingPaused);
+			
+			let rewards = PendingRewards::<T>::get(who);
+			ensure!(!rewards.is_zero(), Error::<T>::NoRewards);
+			
+			// At most one claim per `MinClaimInterval`
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(last_claim) = LastClaimBlock::<T>::get(who) {
+				ensure!(now >= last_claim.saturating_add(T::MinClaimInterval::get()), Error::<T>::ClaimTooSoon);
+			}
+			
+			Ok(rewards)
+		}
+
+		// Clear the pending rewards of `who` and record when they were claimed
+		fn note_claimed(who: &T::AccountId) {
+			PendingRewards::<T>::remove(who);
+			LastClaimBlock::<T>::insert(who, frame_system::Pallet::<T>::block_number());
+			LastClaimedEra::<T>::insert(who, Self::current_era());
+		}
+
+		// Check that `validator` can take new stake from nominators: it is registered, not
+		// under investigation, holds at least `MinStake` and, where required, is active
+		fn ensure_nominatable(validator: &T::AccountId) -> DispatchResult {
+			ensure!(Validators::<T>::contains_key(validator), Error::<T>::NotValidator);
+			ensure!(!UnderInvestigation::<T>::get(validator), Error::<T>::ValidatorUnderInvestigation);
+			
+			// A validator slashed below the minimum stake takes no new nominations
+			ensure!(Validators::<T>::get(validator) >= T::MinStake::get(), Error::<T>::StakeBelowMinimum);
+			
+			// Where configured, only validators that can earn rewards take nominations
+			ensure!(
+				!T::RequireActiveValidatorForNomination::get() || ActiveSet::<T>::get().contains(validator),
+				Error::<T>::ValidatorNotActive
+			);
+			
+			Ok(())
+		}
+
+		// Withdraw the nomination of `who` to `validator`, unbonding it or holding it while
+		// the validator is under investigation. A `forced` withdrawal unbonds even when the
+		// nominator has no unbonding chunk slot left.
+		fn do_withdraw_nomination(who: T::AccountId, validator: T::AccountId, forced: bool) -> DispatchResult {
+			// Get nominations
+			let mut nominations = Nominators::<T>::get(&who);
+			
+			// Find the nomination
+			let position = nominations.iter().position(|n| n.validator == validator)
+				.ok_or(Error::<T>::NominationNotFound)?;
+			
+			// Get the nomination amount
+			let amount = nominations[position].amount;
+			
+			// Remove the nomination
+			nominations.swap_remove(position);
+			Self::put_nominations(&who, nominations)?;
+			Self::reset_bonded_since(&who);
+			
+			// While the validator is under investigation the funds stay reserved until resolution
+			if UnderInvestigation::<T>::get(&validator) {
+				TotalValidatorStake::<T>::mutate(&validator, |total| {
+					*total = total.checked_sub(&amount).unwrap_or(*total);
+				});
+				let old_total = TotalStaked::<T>::get();
+				TotalStaked::<T>::put(old_total.checked_sub(&amount).unwrap_or(old_total));
 				
-				// Process nominators for this validator
-				for (nominator, nominations) in Nominators::<T>::iter() {
-					for nomination in nominations.iter() {
-						if nomination.validator != validator {
-							continue;
-						}
-						
-						// Calculate nominator's reward
-						let nominator_reward = nomination.amount
-							.checked_mul(&Self::NOMINATOR_INFLATION_RATE_NUMERATOR.into())
-							.and_then(|r| r.checked_div(&(Self::BLOCKS_PER_YEAR * Self::NOMINATOR_INFLATION_RATE_DENOMINATOR).into()))
-							.unwrap_or_else(Zero::zero)
-							.checked_mul(&Self::ERA_DURATION.into())
-							.unwrap_or_else(Zero::zero);
-						
-						if !nominator_reward.is_zero() {
-							// Add to nominator's pending rewards
-							PendingRewards::<T>::mutate(&nominator, |rewards| {
-								*rewards = rewards.checked_add(&nominator_reward).unwrap_or(*rewards);
-							});
-							
-							// Create the reward tokens
-							let _ = T::Currency::deposit_creating(&nominator, nominator_reward);
+				HeldWithdrawals::<T>::mutate(&validator, &who, |held| {
+					*held = held.saturating_add(amount);
+				});
+				Self::deposit_event(Event::WithdrawalHeld(who, validator, amount));
+				return Ok(());
+			}
+			
+			// Unbond what is actually still reserved, a slash may have taken part of it
+			let unbonding = T::Currency::reserved_balance(&who).min(amount);
+			if forced {
+				Self::force_unbond(&who, unbonding);
+			} else {
+				Self::schedule_unbond(&who, unbonding)?;
+			}
+			
+			// Update total validator stake
+			TotalValidatorStake::<T>::mutate(&validator, |total| {
+				*total = total.checked_sub(&unbonding).unwrap_or(*total);
+			});
+			
+			// Update total staked
+			let old_total = TotalStaked::<T>::get();
+			let new_total = old_total.checked_sub(&unbonding).unwrap_or(old_total);
+			TotalStaked::<T>::put(new_total);
+			
+			// Emit event
+			Self::deposit_event(Event::NominationWithdrawn(who, validator, unbonding));
+			
+			Ok(())
+		}
+
+		// Store the nominations of `who`, keeping `NominatorsByValidator` in step and
+		// `NominatorCount` when the account makes its first nomination or drops its last one
+		fn put_nominations(
+			who: &T::AccountId,
+			nominations: BoundedVec<Nomination<T::AccountId, BalanceOf<T>>, T::MaxNominations>,
+		) -> DispatchResult {
+			let previous = Nominators::<T>::get(who);
+			let was_nominating = !previous.is_empty();
+			
+			for old in previous.iter().filter(|old| !nominations.iter().any(|n| n.validator == old.validator)) {
+				NominatorsByValidator::<T>::mutate_exists(&old.validator, |maybe_backers| {
+					if let Some(backers) = maybe_backers {
+						backers.retain(|backer| backer != who);
+						if backers.is_empty() {
+							*maybe_backers = None;
 						}
 					}
+				});
+			}
+			for new in nominations.iter().filter(|new| !previous.iter().any(|n| n.validator == new.validator)) {
+				NominatorsByValidator::<T>::try_mutate(&new.validator, |backers| {
+					backers.try_push(who.clone())
+				}).map_err(|_| Error::<T>::ValidatorOversubscribed)?;
+			}
+			
+			if nominations.is_empty() {
+				Nominators::<T>::remove(who);
+				if was_nominating {
+					NominatorCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+				}
+			} else {
+				Nominators::<T>::insert(who, nominations);
+				if !was_nominating {
+					NominatorCount::<T>::mutate(|count| *count = count.saturating_add(1));
 				}
 			}
 			
-			// Emit event with total rewards for the era
-			let total_reward = total_staked
-				.checked_mul(&Self::VALIDATOR_INFLATION_RATE_NUMERATOR.into())
-				.and_then(|r| r.checked_div(&(Self::BLOCKS_PER_YEAR * Self::VALIDATOR_INFLATION_RATE_DENOMINATOR).into()))
-				.unwrap_or_else(Zero::zero)
-				.checked_mul(&Self::ERA_DURATION.into())
-				.unwrap_or_else(Zero::zero);
+			Ok(())
+		}
+
+		// Clear all per-validator state. Pending rewards are kept for the account to claim,
+		// an open misbehavior report stays until it is resolved, and the registration era stays
+		// until the stake snapshots it can still be slashed through are pruned.
+		fn cleanup_validator(who: &T::AccountId) {
+			Validators::<T>::remove(who);
+			TotalValidatorStake::<T>::remove(who);
+			ValidatorCommission::<T>::remove(who);
+			Chilled::<T>::remove(who);
+			ZeroStakeEras::<T>::remove(who);
+			FirstActiveEra::<T>::remove(who);
+			ValidatorMetadata::<T>::remove(who);
+			OffenceHistory::<T>::remove(who);
+			OffenceCount::<T>::remove(who);
+			ActiveSet::<T>::mutate(|active| active.retain(|validator| validator != who));
+		}
+
+		// Move `amount` of reserved stake into an unbonding chunk that unlocks after
+		// `UnbondingPeriod` eras, merging with a chunk that unlocks in the same era
+		fn schedule_unbond(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			if amount.is_zero() {
+				return Ok(());
+			}
+			
+			let unlock_era = Self::current_era().saturating_add(T::UnbondingPeriod::get());
+			Unbonding::<T>::try_mutate(who, |chunks| -> DispatchResult {
+				if let Some(chunk) = chunks.iter_mut().find(|(_, era)| *era == unlock_era) {
+					chunk.0 = chunk.0.saturating_add(amount);
+				} else {
+					chunks
+						.try_push((amount, unlock_era))
+						.map_err(|_| Error::<T>::TooManyUnbondingChunks)?;
+				}
+				Ok(())
+			})?;
+			
+			Self::deposit_event(Event::Unbonded(who.clone(), amount, unlock_era));
+			
+			Ok(())
+		}
+
+		// Unbond `amount` like `schedule_unbond` where the account's own state must not fail
+		// the caller. With every chunk slot taken the amount joins the newest chunk, whose
+		// unlock moves out to this unbond's era so nothing is released early.
+		fn force_unbond(who: &T::AccountId, amount: BalanceOf<T>) {
+			if Self::schedule_unbond(who, amount).is_ok() {
+				return;
+			}
+			
+			let unlock_era = Self::current_era().saturating_add(T::UnbondingPeriod::get());
+			Unbonding::<T>::mutate(who, |chunks| {
+				if let Some(newest) = chunks.iter_mut().max_by_key(|(_, era)| *era) {
+					newest.0 = newest.0.saturating_add(amount);
+					newest.1 = newest.1.max(unlock_era);
+				}
+			});
 			
-			Self::deposit_event(Event::RewardsDistributed(era, total_reward));
-    	}
+			Self::deposit_event(Event::Unbonded(who.clone(), amount, unlock_era));
+		}
 
-		// Helper function to slash a validator
-		fn do_slash(validator: &T::AccountId, slash_amount: BalanceOf<T>) -> DispatchResult {
+		// Helper function to slash a validator by `slash_amount` and its nominators by the
+		// same `slash_percent` of their nominations
+		fn do_slash(
+			validator: &T::AccountId,
+			slash_amount: BalanceOf<T>,
+			slash_percent: u32,
+			category: SlashCategory,
+		) -> DispatchResult {
 			ensure!(!slash_amount.is_zero(), Error::<T>::ZeroSlashAmount);
 
-			let current_stake = Validators::<T>::get(validator);
-			let remaining_stake = current_stake.checked_sub(&slash_amount)
-				.ok_or(Error::<T>::InsufficientStake)?;
+			// Stake still unbonding is taken first, so unbonding after an offence does not
+			// dodge the slash, and the current stake covers the rest
+			let unbonding = Unbonding::<T>::get(validator)
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |sum, (amount, _)| sum.saturating_add(*amount));
+			let from_unbonding = slash_amount.min(unbonding);
+			let from_stake = slash_amount.saturating_sub(from_unbonding);
+			let registered = Validators::<T>::contains_key(validator);
+			if registered {
+				let remaining_stake = Validators::<T>::get(validator).checked_sub(&from_stake)
+					.ok_or(Error::<T>::InsufficientStake)?;
 
-			Validators::<T>::insert(validator, remaining_stake);
+				Validators::<T>::insert(validator, remaining_stake);
 
-			TotalValidatorStake::<T>::mutate(validator, |total| {
-				*total = total.checked_sub(&slash_amount).unwrap_or_else(Zero::zero);
-			});
+				// A validator slashed below the minimum stake stops validating until it tops up
+				if remaining_stake < T::MinStake::get() && !Chilled::<T>::get(validator) {
+					Chilled::<T>::insert(validator, true);
+					Self::deposit_event(Event::ValidatorChilled(validator.clone()));
+				}
+
+				TotalValidatorStake::<T>::mutate(validator, |total| {
+					*total = total.checked_sub(&from_stake).unwrap_or_else(Zero::zero);
+				});
+			} else {
+				// A removed validator's whole stake is unbonding
+				ensure!(from_stake.is_zero(), Error::<T>::InsufficientStake);
+			}
 
 			TotalStaked::<T>::mutate(|total| {
-				*total = total.checked_sub(&slash_amount).unwrap_or_else(Zero::zero);
+				*total = total.checked_sub(&from_stake).unwrap_or_else(Zero::zero);
 			});
 
-			let _ = T::Currency::slash_reserved(validator, slash_amount);
+			if !from_unbonding.is_zero() {
+				Self::slash_unbonding(validator, from_unbonding);
+			}
+
+			let (slashed, _) = T::Currency::slash_reserved(validator, slash_amount);
+			T::OnSlash::on_unbalanced(slashed);
+			
+			// Nominators are not held responsible for downtime
+			if category == SlashCategory::Malice {
+				Self::slash_nominators(validator, slash_percent);
+			}
+			
+			// A validator left with too little of its own stake at risk stops validating too
+			if registered && Self::below_min_self_stake_ratio(validator) && !Chilled::<T>::get(validator) {
+				Chilled::<T>::insert(validator, true);
+				Self::deposit_event(Event::ValidatorChilled(validator.clone()));
+			}
 			
 			Ok(())
 		}
+
+		// Take `amount` out of the unbonding chunks of `who`, latest unlock first
+		fn slash_unbonding(who: &T::AccountId, amount: BalanceOf<T>) {
+			Unbonding::<T>::mutate_exists(who, |maybe_chunks| {
+				let Some(chunks) = maybe_chunks else {
+					return;
+				};
+				let mut remaining = amount;
+				for (chunk, _) in chunks.iter_mut().rev() {
+					let taken = remaining.min(*chunk);
+					*chunk = chunk.saturating_sub(taken);
+					remaining = remaining.saturating_sub(taken);
+				}
+				chunks.retain(|(chunk, _)| !chunk.is_zero());
+				if chunks.is_empty() {
+					*maybe_chunks = None;
+				}
+			});
+		}
+
+		// Slash `slash_percent` of every nomination backing `validator` and of every withdrawal
+		// from it held under investigation
+		fn slash_nominators(validator: &T::AccountId, slash_percent: u32) {
+			for nominator in NominatorsByValidator::<T>::get(validator) {
+				let slashed = Nominators::<T>::mutate(&nominator, |nominations| {
+					let nomination = nominations.iter_mut().find(|n| n.validator == *validator)?;
+					let amount = nomination.amount
+						.checked_mul(&slash_percent.into())
+						.and_then(|r| r.checked_div(&100u32.into()))
+						.unwrap_or_else(Zero::zero);
+					
+					// Only what is still reserved can be taken
+					let (imbalance, unslashed) = T::Currency::slash_reserved(&nominator, amount);
+					T::OnSlash::on_unbalanced(imbalance);
+					let slashed = amount.saturating_sub(unslashed);
+					nomination.amount = nomination.amount.saturating_sub(slashed);
+					Some(slashed)
+				});
+				let Some(slashed) = slashed.filter(|slashed| !slashed.is_zero()) else {
+					continue;
+				};
+				
+				TotalValidatorStake::<T>::mutate(validator, |total| {
+					*total = total.checked_sub(&slashed).unwrap_or_else(Zero::zero);
+				});
+				TotalStaked::<T>::mutate(|total| {
+					*total = total.checked_sub(&slashed).unwrap_or_else(Zero::zero);
+				});
+				
+				Self::deposit_event(Event::NominatorSlashed(nominator, validator.clone(), slashed));
+			}
+			
+			// Withdrawals held during an investigation still answer for the validator's offences
+			let held: Vec<_> = HeldWithdrawals::<T>::iter_prefix(validator).collect();
+			for (nominator, amount) in held {
+				let slash = amount
+					.checked_mul(&slash_percent.into())
+					.and_then(|r| r.checked_div(&100u32.into()))
+					.unwrap_or_else(Zero::zero);
+				let (imbalance, unslashed) = T::Currency::slash_reserved(&nominator, slash);
+				T::OnSlash::on_unbalanced(imbalance);
+				let slashed = slash.saturating_sub(unslashed);
+				if slashed.is_zero() {
+					continue;
+				}
+				
+				let remaining = amount.saturating_sub(slashed);
+				if remaining.is_zero() {
+					HeldWithdrawals::<T>::remove(validator, &nominator);
+				} else {
+					HeldWithdrawals::<T>::insert(validator, &nominator, remaining);
+				}
+				
+				Self::deposit_event(Event::NominatorSlashed(nominator, validator.clone(), slashed));
+			}
+		}
+	}
+}
+
+/// Feeds the elected validators to `pallet_session`. Each new session uses the active set
+/// elected at the start of the current era, the `MaxActiveValidators` validators with the
+/// highest total stake, so block production and rewards follow the same validators.
+impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+	fn new_session(_new_index: u32) -> Option<alloc::vec::Vec<T::AccountId>> {
+		// Keep the previous validators rather than handing the session an empty set
+		let active = ActiveSet::<T>::get();
+		(!active.is_empty()).then(|| active.into_inner())
 	}
+
+	fn end_session(_end_index: u32) {}
+
+	fn start_session(_start_index: u32) {}
 }
\ No newline at end of file
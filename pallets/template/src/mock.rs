@@ -1,12 +1,14 @@
 use crate as pallet_template;
 use frame_support::{
-	parameter_types, traits::{ConstU16, ConstU32, ConstU64, ConstU128}
+	ord_parameter_types, parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Currency, EitherOfDiverse, OnUnbalanced},
 };
 use frame_system as system;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	BuildStorage, Perbill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -47,17 +49,18 @@ impl system::Config for Test {
 	type MaxConsumers = ConstU32<16>;
 	type RuntimeTask = ();
 	type SingleBlockMigrations = ();
-	type MultiBlockMigrations = ();
+	type MultiBlockMigrator = ();
 	type PreInherents = ();
 	type PostInherents = ();
 	type PostTransactions = ();
+	type ExtensionsWeightInfo = ();
 }
 
 impl pallet_balances::Config for Test {
 	type Balance = Balance;
 	type DustRemoval = ();
 	type RuntimeEvent = RuntimeEvent;
-	type ExistentialDeposit = ConstU128<1>;
+	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
 	type MaxLocks = ();
@@ -67,13 +70,60 @@ impl pallet_balances::Config for Test {
 	type RuntimeFreezeReason = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
+	type DoneSlashHandler = ();
 }
 
 parameter_types! {
-	pub const MinStake: Balance = 100;
+	pub static ExistentialDeposit: Balance = 1;
+	pub static MinStake: Balance = 100;
 	pub const MinNomination: Balance = 10;
 	pub const MaxNominations: u32 = 4;
 	pub const RewardRate: u32 = 100; // 1% for testing
+	pub const MaxBatchSlashes: u32 = 4;
+	pub const MaxPendingReward: Balance = 1_000_000;
+	pub const MaxRewardGrowth: u32 = 10;
+	pub static RewardStartEra: pallet_template::EraIndex = 0;
+	pub const MaxActiveValidators: u32 = 2;
+	pub static RewardActiveSetOnly: bool = false;
+	pub static RequireActiveValidatorForNomination: bool = false;
+	pub const MaxNominatorsPerValidator: u32 = 16;
+	pub static MinProvingEras: pallet_template::EraIndex = 0;
+	pub static SlashGracePeriod: pallet_template::EraIndex = 0;
+	pub const UnbondingPeriod: pallet_template::EraIndex = 2;
+	pub const MaxUnbondingChunks: u32 = 4;
+	pub static MinClaimInterval: u64 = 0;
+	pub static MaxValidators: u32 = 16;
+	pub static MaxNewValidatorsPerEra: u32 = 16;
+	pub const HistoryDepth: pallet_template::EraIndex = 3;
+	pub static RequireWhitelist: bool = false;
+	pub const MaxZeroStakeEras: u32 = 2;
+	pub static TreasuryCut: Perbill = Perbill::zero();
+	pub const TreasuryAccount: u64 = 98;
+	pub static DustHandling: pallet_template::DustPolicy<u64> = pallet_template::DustPolicy::KeepAlive;
+	pub static EraDuration: u64 = 14_400;
+	pub const ValidatorInflation: Perbill = Perbill::from_percent(15);
+	pub const NominatorInflation: Perbill = Perbill::from_percent(10);
+	pub static MinNominators: u32 = 0;
+	pub static MaxLoyaltyBonus: Perbill = Perbill::zero();
+	pub const LoyaltyRampEras: pallet_template::EraIndex = 4;
+	pub const MaxOffenceHistory: u32 = 2;
+	pub static SoftCommissionCap: Perbill = Perbill::one();
+	pub static RedirectExcessCommission: bool = true;
+	pub static MinSelfStakeRatio: Perbill = Perbill::zero();
+}
+
+// Account collecting slashed funds
+pub const SLASH_DESTINATION: u64 = 97;
+
+pub struct SlashToDestination;
+impl OnUnbalanced<pallet_template::NegativeImbalanceOf<Test>> for SlashToDestination {
+	fn on_nonzero_unbalanced(amount: pallet_template::NegativeImbalanceOf<Test>) {
+		Balances::resolve_creating(&SLASH_DESTINATION, amount);
+	}
+}
+
+ord_parameter_types! {
+	pub const SlashCommittee: u64 = 99;
 }
 
 impl pallet_template::Config for Test {
@@ -83,11 +133,49 @@ impl pallet_template::Config for Test {
 	type MinNomination = MinNomination;
 	type MaxNominations = MaxNominations;
 	type RewardRate = RewardRate;
+	type MaxPendingReward = MaxPendingReward;
+	type MaxBatchSlashes = MaxBatchSlashes;
+	type MaxRewardGrowth = MaxRewardGrowth;
+	type RewardStartEra = RewardStartEra;
+	type MaxActiveValidators = MaxActiveValidators;
+	type RewardActiveSetOnly = RewardActiveSetOnly;
+	type RequireActiveValidatorForNomination = RequireActiveValidatorForNomination;
+	type MaxNominatorsPerValidator = MaxNominatorsPerValidator;
+	type MinProvingEras = MinProvingEras;
+	type SlashGracePeriod = SlashGracePeriod;
+	type UnbondingPeriod = UnbondingPeriod;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type MinClaimInterval = MinClaimInterval;
+	type MaxValidators = MaxValidators;
+	type MaxNewValidatorsPerEra = MaxNewValidatorsPerEra;
+	type HistoryDepth = HistoryDepth;
+	type RequireWhitelist = RequireWhitelist;
+	type MaxZeroStakeEras = MaxZeroStakeEras;
+	type TreasuryCut = TreasuryCut;
+	type TreasuryAccount = TreasuryAccount;
+	type DustHandling = DustHandling;
+	type EraDuration = EraDuration;
+	type ValidatorInflation = ValidatorInflation;
+	type NominatorInflation = NominatorInflation;
+	type MinNominators = MinNominators;
+	type MaxLoyaltyBonus = MaxLoyaltyBonus;
+	type LoyaltyRampEras = LoyaltyRampEras;
+	type MaxOffenceHistory = MaxOffenceHistory;
+	type SoftCommissionCap = SoftCommissionCap;
+	type RedirectExcessCommission = RedirectExcessCommission;
+	type MinSelfStakeRatio = MinSelfStakeRatio;
+	type OnSlash = SlashToDestination;
+	type SlashOrigin = EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<SlashCommittee, u64>>;
 	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with_validators(vec![])
+}
+
+// Build genesis storage with validators registered at genesis.
+pub fn new_test_ext_with_validators(initial_validators: Vec<(u64, Balance)>) -> sp_io::TestExternalities {
 	let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap();
 	
 	pallet_balances::GenesisConfig::<Test> {
@@ -97,10 +185,23 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			(3, 1000),
 			(4, 1000),
 			(5, 1000),
+			// Large stakers whose stake is big enough to earn non-zero era rewards
+			(10, 1_000_000_000_000),
+			(11, 1_000_000_000_000),
+			(12, 1_000_000_000_000),
+			(13, 1_000_000_000_000),
 		],
+		dev_accounts: None,
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
 	
-	t.into()
+	pallet_template::GenesisConfig::<Test> { initial_validators }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	
+	let mut ext: sp_io::TestExternalities = t.into();
+	// Events are not recorded at genesis, so start tests at block 1
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
\ No newline at end of file
@@ -1,5 +1,76 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	mock::*, CommissionEarned, DustPolicy, Error, Event, Nomination, NominatorDashboard, PendingRewards, RewardDestination,
+	SlashCategory, ValidatorStatus,
+};
+use frame_support::{
+	assert_noop, assert_ok, BoundedVec,
+	traits::{Currency, Hooks, ReservableCurrency},
+};
+use pallet_session::SessionManager;
+use sp_runtime::Perbill;
+
+// Advance to the first block of the next era and run the era rotation
+fn run_to_next_era() {
+	let next = TemplateModule::era_start_block() + EraDuration::get();
+	System::set_block_number(next);
+	TemplateModule::on_initialize(next);
+}
+
+// Run the era rotation like `run_to_next_era` and check that no reward is lost or minted twice:
+// what `simulate_era_rewards` predicted is exactly what was credited to pending rewards and
+// commission or paid out early, and the treasury's cut and rounding residual make up the rest
+// of the minted pool
+fn run_to_next_era_conserving() {
+	let era = TemplateModule::current_era();
+	let predicted: u128 = TemplateModule::simulate_era_rewards().iter().map(|(_, reward)| reward).sum();
+	let owed_before = owed_rewards();
+	let treasury_before = Balances::free_balance(TreasuryAccount::get());
+	let prev_pool = TemplateModule::prev_era_pool();
+	let ceiling = prev_pool + Perbill::from_percent(MaxRewardGrowth::get()).mul_floor(prev_pool);
+	let first_event = System::events().len();
+	
+	run_to_next_era();
+	
+	let mut paid_early = 0;
+	let mut residual = 0;
+	for record in &System::events()[first_event..] {
+		match record.event {
+			RuntimeEvent::TemplateModule(Event::RewardsClaimed(_, amount)) => paid_early += amount,
+			RuntimeEvent::TemplateModule(Event::RewardResidual(_, amount)) => residual += amount,
+			_ => {},
+		}
+	}
+	let credited = owed_rewards() + paid_early - owed_before;
+	let distributed = TemplateModule::era_total_rewards(era);
+	assert_eq!(credited, predicted, "era {era}: credited rewards differ from the prediction");
+	assert_eq!(credited, distributed, "era {era}: credited rewards differ from the recorded total");
+	
+	if distributed == 0 {
+		return;
+	}
+	let treasury = Balances::free_balance(TreasuryAccount::get()) - treasury_before;
+	let minted = TemplateModule::prev_era_pool();
+	assert_eq!(minted, distributed + treasury, "era {era}: minted pool differs from its payouts");
+	assert!(treasury >= residual, "era {era}: residual was not paid to the treasury");
+	if prev_pool > 0 {
+		assert!(minted <= ceiling, "era {era}: minted pool exceeds the growth ceiling");
+	}
+}
+
+// Rewards and commission credited but not yet minted, across all accounts
+fn owed_rewards() -> u128 {
+	PendingRewards::<Test>::iter_values().sum::<u128>() + CommissionEarned::<Test>::iter_values().sum::<u128>()
+}
+
+// Deterministic generator for the randomized staking tests
+struct Lcg(u64);
+
+impl Lcg {
+	fn below(&mut self, bound: u64) -> u64 {
+		self.0 = self.0.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+		(self.0 >> 33) % bound
+	}
+}
 
 #[test]
 fn register_validator_works() {
@@ -63,6 +134,125 @@ fn register_validator_fails_with_insufficient_balance() {
 	});
 }
 
+#[test]
+fn register_validator_requires_whitelist_when_enabled() {
+	new_test_ext().execute_with(|| {
+		RequireWhitelist::set(true);
+		
+		assert_noop!(
+			TemplateModule::register_validator(RuntimeOrigin::signed(1), 500),
+			Error::<Test>::NotWhitelisted
+		);
+		
+		// Only root curates the whitelist
+		assert_noop!(
+			TemplateModule::add_to_whitelist(RuntimeOrigin::signed(1), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::add_to_whitelist(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::AddedToWhitelist(1).into());
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		// Removal blocks future registrations but keeps the validator
+		assert_ok!(TemplateModule::remove_from_whitelist(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::RemovedFromWhitelist(1).into());
+		assert_eq!(TemplateModule::validators(1), 500);
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			TemplateModule::register_validator(RuntimeOrigin::signed(1), 100),
+			Error::<Test>::NotWhitelisted
+		);
+	});
+}
+
+#[test]
+fn genesis_registers_initial_validators() {
+	new_test_ext_with_validators(vec![(1, 500), (2, 300)]).execute_with(|| {
+		assert_eq!(TemplateModule::validators(1), 500);
+		assert_eq!(TemplateModule::validators(2), 300);
+		assert_eq!(TemplateModule::total_validator_stake(1), 500);
+		assert_eq!(TemplateModule::validator_count(), 2);
+		assert_eq!(TemplateModule::total_staked(), 800);
+		assert_eq!(Balances::reserved_balance(1), 500);
+		assert_eq!(TemplateModule::active_set().into_inner(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn new_session_returns_top_validators_by_total_stake() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		// No active set yet, so the session keeps its validators
+		assert_eq!(<TemplateModule as SessionManager<u64>>::new_session(1), None);
+		
+		for (validator, stake) in [(1, 300), (2, 500), (3, 200), (4, 600), (5, 100)] {
+			assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), stake));
+		}
+		// Nominations count towards the total stake, tying validator 3 with validator 2
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(10), 3, 300));
+		run_to_next_era();
+		
+		// The top two by total stake, the tie going to the lower account id
+		assert_eq!(<TemplateModule as SessionManager<u64>>::new_session(2), Some(vec![4, 2]));
+	});
+}
+
+#[test]
+#[should_panic(expected = "Initial validator stake is below MinStake")]
+fn genesis_rejects_initial_stake_below_minimum() {
+	new_test_ext_with_validators(vec![(1, 99)]);
+}
+
+#[test]
+fn register_validator_fails_above_validator_cap() {
+	new_test_ext().execute_with(|| {
+		MaxValidators::set(3);
+		
+		// Registrations in the same block all count towards the cap
+		for validator in 1..=3 {
+			assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), 100));
+		}
+		assert_eq!(TemplateModule::validator_count(), 3);
+		assert_noop!(
+			TemplateModule::register_validator(RuntimeOrigin::signed(4), 100),
+			Error::<Test>::TooManyValidators
+		);
+		
+		// Leaving frees a slot
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(3)));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(4), 100));
+	});
+}
+
+#[test]
+fn register_validator_fails_above_per_era_cap() {
+	new_test_ext().execute_with(|| {
+		MaxNewValidatorsPerEra::set(2);
+		
+		for validator in 1..=2 {
+			assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), 100));
+		}
+		assert_eq!(TemplateModule::new_validators_this_era(), 2);
+		assert_noop!(
+			TemplateModule::register_validator(RuntimeOrigin::signed(3), 100),
+			Error::<Test>::TooManyNewValidatorsThisEra
+		);
+		
+		// Leaving does not free a slot within the era
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(2)));
+		assert_noop!(
+			TemplateModule::register_validator(RuntimeOrigin::signed(3), 100),
+			Error::<Test>::TooManyNewValidatorsThisEra
+		);
+		
+		// The count resets at the next era
+		run_to_next_era();
+		assert_eq!(TemplateModule::new_validators_this_era(), 0);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(3), 100));
+	});
+}
+
 #[test]
 fn remove_validator_works() {
 	new_test_ext().execute_with(|| {
@@ -83,6 +273,90 @@ fn remove_validator_works() {
 	});
 }
 
+#[test]
+fn removing_validator_clears_its_state() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		for validator in [10, 11] {
+			assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(validator), Perbill::from_percent(5)));
+		}
+		run_to_next_era();
+		assert_eq!(TemplateModule::active_set().to_vec(), vec![10, 11]);
+		let pending = TemplateModule::pending_rewards(10);
+		assert!(pending > 0);
+		
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(10)));
+		assert_ok!(TemplateModule::force_remove_validator(RuntimeOrigin::root(), 11));
+		
+		for validator in [10, 11] {
+			assert!(!crate::Validators::<Test>::contains_key(validator));
+			assert!(!crate::TotalValidatorStake::<Test>::contains_key(validator));
+			assert!(!crate::ValidatorCommission::<Test>::contains_key(validator));
+			assert_eq!(TemplateModule::first_active_era(validator), None);
+		}
+		assert!(TemplateModule::active_set().is_empty());
+		assert_eq!(TemplateModule::validator_count(), 0);
+		
+		// Pending rewards survive the teardown
+		assert_eq!(TemplateModule::pending_rewards(10), pending);
+		
+		assert_noop!(
+			TemplateModule::force_remove_validator(RuntimeOrigin::signed(1), 10),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn removing_validator_unbonds_its_nominations() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_eq!(TemplateModule::total_staked(), 600);
+		
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		System::assert_has_event(Event::NominationWithdrawn(2, 1, 100).into());
+		assert!(TemplateModule::nominators(2).is_empty());
+		assert!(TemplateModule::nominators_by_validator(1).is_empty());
+		assert_eq!(TemplateModule::unbonding(2).to_vec(), vec![(100, 2)]);
+		assert_eq!(TemplateModule::nominator_count(), 0);
+		assert_eq!(TemplateModule::total_staked(), 0);
+		
+		// Registering again starts from the validator's own stake, not the old nominations
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 300));
+		assert_eq!(TemplateModule::total_validator_stake(1), 300);
+	});
+}
+
+#[test]
+fn backer_with_full_unbonding_chunks_does_not_block_removal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(3), 500));
+		
+		// The backer fills every unbonding chunk slot, one per era
+		for _ in 0..MaxUnbondingChunks::get() {
+			assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 3, 50));
+			assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 3));
+			run_to_next_era();
+		}
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_noop!(
+			TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::TooManyUnbondingChunks
+		);
+		
+		// Removing the validator still unbonds the nomination, joining the newest chunk and
+		// unlocking no earlier than a fresh chunk would
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		assert!(TemplateModule::nominators(2).is_empty());
+		assert_eq!(TemplateModule::unbonding(2).to_vec(), vec![(50, 2), (50, 3), (50, 4), (150, 6)]);
+		assert_eq!(Balances::reserved_balance(2), 300);
+	});
+}
+
 #[test]
 fn remove_validator_fails_when_not_validator() {
 	new_test_ext().execute_with(|| {
@@ -95,158 +369,2387 @@ fn remove_validator_fails_when_not_validator() {
 }
 
 #[test]
-fn nominate_works() {
+fn bond_extra_works() {
 	new_test_ext().execute_with(|| {
-		// Register validator
 		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
-		
-		// Nominate validator
 		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
 		
-		// Check nomination is stored
-		let nominations = TemplateModule::nominators(2);
-		assert_eq!(nominations.len(), 1);
-		assert_eq!(nominations[0].validator, 1);
-		assert_eq!(nominations[0].amount, 100);
+		// Add to the validator's self stake
+		assert_ok!(TemplateModule::bond_extra(RuntimeOrigin::signed(1), 200));
 		
-		// Check total validator stake is updated
-		assert_eq!(TemplateModule::total_validator_stake(1), 600);
+		// Self stake, totals and reserve all grow by the increase
+		assert_eq!(TemplateModule::validators(1), 700);
+		assert_eq!(TemplateModule::total_validator_stake(1), 800);
+		assert_eq!(TemplateModule::total_staked(), 800);
+		assert_eq!(Balances::reserved_balance(1), 700);
 		
-		// Check total staked is updated
-		assert_eq!(TemplateModule::total_staked(), 600);
+		System::assert_has_event(Event::ValidatorBondedExtra(1, 200).into());
+		System::assert_last_event(Event::ValidatorStakeIncreased(1, 700).into());
 		
-		// System emits event
-		System::assert_has_event(Event::Nomination(2, 1, 100).into());
+		// Bonding again adds on top of the grown stake
+		assert_ok!(TemplateModule::bond_extra(RuntimeOrigin::signed(1), 100));
+		System::assert_last_event(Event::ValidatorStakeIncreased(1, 800).into());
+		assert_eq!(TemplateModule::validators(1), 800);
+		assert_eq!(TemplateModule::total_validator_stake(1), 900);
+		assert_eq!(TemplateModule::total_staked(), 900);
+		
+		// Cannot reserve more than the free balance
+		assert_noop!(
+			TemplateModule::bond_extra(RuntimeOrigin::signed(1), 300),
+			Error::<Test>::InsufficientBalance
+		);
 	});
 }
 
 #[test]
-fn nominate_fails_when_validator_not_exists() {
+fn bond_extra_fails_when_not_validator() {
 	new_test_ext().execute_with(|| {
-		// Try to nominate non-existent validator
 		assert_noop!(
-			TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100),
+			TemplateModule::bond_extra(RuntimeOrigin::signed(1), 100),
 			Error::<Test>::NotValidator
 		);
 	});
 }
 
 #[test]
-fn nominate_fails_with_insufficient_amount() {
+fn unbond_reduces_stake_down_to_minimum() {
 	new_test_ext().execute_with(|| {
-		// Register validator
 		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
 		
-		// Try to nominate with less than minimum
-		assert_noop!(
-			TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 5),
-			Error::<Test>::NominationBelowMinimum
-		);
+		// Down to exactly MinStake
+		assert_ok!(TemplateModule::unbond(RuntimeOrigin::signed(1), 400));
+		System::assert_last_event(Event::ValidatorUnbonded(1, 400).into());
+		assert_eq!(TemplateModule::validators(1), 100);
+		assert_eq!(TemplateModule::total_validator_stake(1), 200);
+		assert_eq!(TemplateModule::total_staked(), 200);
+		
+		// Still reserved until the unbonding period has passed
+		assert_eq!(Balances::reserved_balance(1), 500);
+		assert_eq!(TemplateModule::unbonding(1).into_inner(), vec![(400, 2)]);
+		run_to_next_era();
+		run_to_next_era();
+		assert_ok!(TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 100);
 	});
 }
 
 #[test]
-fn nominate_fails_when_already_nominated() {
+fn unbond_fails_below_minimum() {
 	new_test_ext().execute_with(|| {
-		// Register validator
 		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
 		
-		// Nominate validator
-		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
-		
-		// Try to nominate again
 		assert_noop!(
-			TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100),
-			Error::<Test>::AlreadyNominated
+			TemplateModule::unbond(RuntimeOrigin::signed(1), 401),
+			Error::<Test>::StakeBelowMinimum
+		);
+		assert_noop!(
+			TemplateModule::unbond(RuntimeOrigin::signed(1), 600),
+			Error::<Test>::InsufficientStake
+		);
+		assert_noop!(
+			TemplateModule::unbond(RuntimeOrigin::signed(2), 100),
+			Error::<Test>::NotValidator
 		);
 	});
 }
 
 #[test]
-fn withdraw_nomination_works() {
+fn chilled_validator_earns_nothing_until_unchilled() {
 	new_test_ext().execute_with(|| {
-		// Register validator
-		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
 		
-		// Nominate validator
-		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_ok!(TemplateModule::chill(RuntimeOrigin::signed(10)));
+		System::assert_last_event(Event::ValidatorChilled(10).into());
+		assert_noop!(TemplateModule::chill(RuntimeOrigin::signed(10)), Error::<Test>::AlreadyChilled);
 		
-		// Withdraw nomination
-		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		// Neither the validator nor its nominator earn, and it is not elected
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(10), 0);
+		assert_eq!(TemplateModule::pending_rewards(11), 0);
+		assert!(TemplateModule::active_set().is_empty());
 		
-		// Check nomination is removed
-		let nominations = TemplateModule::nominators(2);
-		assert_eq!(nominations.len(), 0);
+		// Stake and nominations are kept
+		assert_eq!(TemplateModule::validators(10), 1_000_000_000);
+		assert_eq!(TemplateModule::total_validator_stake(10), 2_000_000_000);
+		assert_eq!(TemplateModule::backer_count(&10), 1);
 		
-		// Check total validator stake is updated
-		assert_eq!(TemplateModule::total_validator_stake(1), 500);
+		assert_ok!(TemplateModule::unchill(RuntimeOrigin::signed(10)));
+		System::assert_last_event(Event::ValidatorUnchilled(10).into());
+		assert_noop!(TemplateModule::unchill(RuntimeOrigin::signed(10)), Error::<Test>::NotChilled);
 		
-		// Check total staked is updated
-		assert_eq!(TemplateModule::total_staked(), 500);
+		run_to_next_era();
+		assert!(TemplateModule::pending_rewards(10) > 0);
+		assert!(TemplateModule::pending_rewards(11) > 0);
+		assert_eq!(TemplateModule::active_set().into_inner(), vec![10]);
+	});
+}
+
+#[test]
+fn validator_without_stake_is_chilled_after_max_zero_stake_eras() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		// Without a minimum stake the slash itself does not chill
+		MinStake::set(0);
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 100, 0, SlashCategory::Malice));
+		assert_eq!(TemplateModule::total_validator_stake(1), 0);
 		
-		// System emits event
-		System::assert_has_event(Event::NominationWithdrawn(2, 1, 100).into());
+		run_to_next_era();
+		assert_eq!(TemplateModule::zero_stake_eras(1), 1);
+		assert!(!TemplateModule::chilled(1));
+		
+		// The second consecutive era without stake chills it
+		run_to_next_era();
+		assert!(TemplateModule::chilled(1));
+		System::assert_has_event(Event::ValidatorChilled(1).into());
+		assert_eq!(TemplateModule::zero_stake_eras(1), 0);
 	});
 }
 
 #[test]
-fn withdraw_nomination_fails_when_not_nominated() {
+fn zero_stake_counter_resets_when_stake_returns() {
 	new_test_ext().execute_with(|| {
-		// Register validator
 		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		// Without a minimum stake the slash itself does not chill
+		MinStake::set(0);
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 100, 0, SlashCategory::Malice));
+		run_to_next_era();
+		assert_eq!(TemplateModule::zero_stake_eras(1), 1);
 		
-		// Try to withdraw non-existent nomination
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		run_to_next_era();
+		assert_eq!(TemplateModule::zero_stake_eras(1), 0);
+		assert!(!TemplateModule::chilled(1));
+	});
+}
+
+#[test]
+fn validator_slashed_below_minimum_is_inactive() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 10, 100));
+		MinStake::set(600_000_000);
+		
+		// A 50% slash leaves validator 10 below the minimum, which chills it
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 10, 50, 0, SlashCategory::Malice));
+		assert_eq!(TemplateModule::validators(10), 500_000_000);
+		assert!(TemplateModule::chilled(10));
+		System::assert_has_event(Event::ValidatorChilled(10).into());
+		
+		// No new nominations, and no way back in without topping up
 		assert_noop!(
-			TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1),
-			Error::<Test>::NominationNotFound
+			TemplateModule::nominate(RuntimeOrigin::signed(3), 10, 100),
+			Error::<Test>::StakeBelowMinimum
 		);
+		assert_noop!(TemplateModule::unchill(RuntimeOrigin::signed(10)), Error::<Test>::StakeBelowMinimum);
+		
+		// It earns nothing for the era, the other validator is paid as usual
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(10), 0);
+		assert_eq!(TemplateModule::pending_rewards(2), 0);
+		assert_eq!(TemplateModule::pending_rewards(11), 403_200);
+		
+		// Topping up restores it
+		assert_ok!(TemplateModule::bond_extra(RuntimeOrigin::signed(10), 100_000_000));
+		assert_ok!(TemplateModule::unchill(RuntimeOrigin::signed(10)));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 10, 100));
 	});
 }
 
 #[test]
-fn rewards_distribution_works() {
+fn validator_below_min_self_stake_ratio_is_chilled_after_slash() {
 	new_test_ext().execute_with(|| {
-		// Register validator
+		System::set_block_number(1);
+		MinSelfStakeRatio::set(Perbill::from_percent(50));
 		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 400));
+		assert_eq!(TemplateModule::self_stake_ratio(&1), Perbill::from_rational(500u32, 900u32));
 		
-		// Nominate validator
-		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 500));
+		// Downtime slashes only the validator, 450 of 850 is still above the floor
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 10, 0, SlashCategory::Downtime));
+		assert_eq!(TemplateModule::self_stake_ratio(&1), Perbill::from_rational(450u32, 850u32));
+		assert!(!TemplateModule::chilled(1));
 		
-		// Advance blocks to trigger reward distribution
-		System::set_block_number(2);
-		TemplateModule::on_initialize(2);
+		// 360 of 760 is not
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Downtime));
+		assert_eq!(TemplateModule::validators(1), 360);
+		assert!(TemplateModule::self_stake_ratio(&1) < Perbill::from_percent(50));
+		assert!(TemplateModule::chilled(1));
+		System::assert_has_event(Event::ValidatorChilled(1).into());
 		
-		// Check rewards were distributed
-		assert!(TemplateModule::pending_rewards(1) > 0);
-		assert!(TemplateModule::pending_rewards(2) > 0);
+		// Unchilling needs the ratio restored
+		assert_noop!(TemplateModule::unchill(RuntimeOrigin::signed(1)), Error::<Test>::SelfStakeRatioTooLow);
+		assert_ok!(TemplateModule::bond_extra(RuntimeOrigin::signed(1), 40));
+		assert_ok!(TemplateModule::unchill(RuntimeOrigin::signed(1)));
+	});
+}
+
+#[test]
+fn self_stake_ratio_is_not_enforced_by_default() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 200));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 900));
 		
-		// Claim rewards for validator
-		let validator_rewards = TemplateModule::pending_rewards(1);
-		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(1)));
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 10, 0, SlashCategory::Downtime));
+		assert_eq!(TemplateModule::self_stake_ratio(&1), Perbill::from_rational(180u32, 1_080u32));
+		assert!(!TemplateModule::chilled(1));
 		
-		// Check rewards were claimed
-		assert_eq!(TemplateModule::pending_rewards(1), 0);
+		// Accounts without stake have no ratio
+		assert_eq!(TemplateModule::self_stake_ratio(&3), Perbill::zero());
+	});
+}
+
+#[test]
+fn validators_need_min_nominators_to_earn() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MinNominators::set(2);
 		
-		// Claim rewards for nominator
-		let nominator_rewards = TemplateModule::pending_rewards(2);
-		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(2)));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(1), 10, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 10, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 11, 100));
 		
-		// Check rewards were claimed
-		assert_eq!(TemplateModule::pending_rewards(2), 0);
+		run_to_next_era();
 		
-		// System emits events
-		System::assert_has_event(Event::RewardsClaimed(1, validator_rewards).into());
-		System::assert_has_event(Event::RewardsClaimed(2, nominator_rewards).into());
+		// Validator 10 meets the threshold, validator 11 with one nominator earns nothing
+		assert_eq!(TemplateModule::pending_rewards(10), 403_200);
+		assert_eq!(TemplateModule::pending_rewards(11), 0);
+		System::assert_has_event(Event::RewardsDistributed(0, 403_200).into());
 	});
 }
 
 #[test]
-fn claim_rewards_fails_when_no_rewards() {
+fn loyalty_bonus_rewards_continuous_bonding() {
 	new_test_ext().execute_with(|| {
-		// Try to claim rewards when none exist
+		MaxLoyaltyBonus::set(Perbill::from_percent(10));
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(12), 10, 1_000_000_000));
+		assert_eq!(TemplateModule::bonded_since_era(11), Some(0));
+		run_to_next_era_conserving();
+		run_to_next_era_conserving();
+		
+		// Withdrawing resets the bonding period, withdrawing everything ends it
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(12), 10));
+		assert_eq!(TemplateModule::bonded_since_era(12), None);
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(12), 10, 1_000_000_000));
+		assert_eq!(TemplateModule::bonded_since_era(12), Some(2));
+		run_to_next_era_conserving();
+		
+		// Each nomination's share of the 950_400 pool is 316_799. Bonded for two of the four
+		// ramp eras, nominator 11 earns half the 10% bonus on top, the fresh nominator none.
+		assert_eq!(TemplateModule::era_rewards(2, 11), 316_799 + 15_839);
+		assert_eq!(TemplateModule::era_rewards(2, 12), 316_799);
+		
+		// The bonus stops growing at the cap
+		run_to_next_era_conserving();
+		run_to_next_era_conserving();
+		run_to_next_era_conserving();
+		assert_eq!(TemplateModule::era_rewards(4, 11), 316_799 + 31_679);
+		assert_eq!(TemplateModule::era_rewards(5, 11), 316_799 + 31_679);
+	});
+}
+
+#[test]
+fn prune_below_minimum_removes_at_most_limit_validators() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		for validator in [1, 2, 3] {
+			assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), 150));
+		}
+		MinStake::set(200);
+		
+		assert_ok!(TemplateModule::prune_below_minimum(RuntimeOrigin::root(), 2));
+		assert_eq!(TemplateModule::validator_count(), 1);
+		
+		// A later prune picks up the rest
+		assert_ok!(TemplateModule::prune_below_minimum(RuntimeOrigin::root(), 2));
+		assert_eq!(TemplateModule::validator_count(), 0);
+	});
+}
+
+#[test]
+fn prune_below_minimum_removes_under_staked_validators() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 150));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(2), 500));
+		
+		// Governance raises the minimum above validator 1's stake
+		MinStake::set(200);
+		assert_ok!(TemplateModule::prune_below_minimum(RuntimeOrigin::root(), 10));
+		
+		// Validator 1 is removed with its stake unbonding
+		assert_eq!(TemplateModule::validators(1), 0);
+		assert_eq!(TemplateModule::unbonding(1).to_vec(), vec![(150, 2)]);
+		System::assert_has_event(Event::ValidatorDeactivated(1, 150).into());
+		
+		// Validator 2 is untouched
+		assert_eq!(TemplateModule::validators(2), 500);
+		assert_eq!(TemplateModule::validator_count(), 1);
+		assert_eq!(TemplateModule::total_staked(), 500);
+		
 		assert_noop!(
-			TemplateModule::claim_rewards(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoRewards
+			TemplateModule::prune_below_minimum(RuntimeOrigin::signed(1), 10),
+			sp_runtime::DispatchError::BadOrigin
 		);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn validator_metadata_can_be_set_and_overwritten() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_noop!(
+			TemplateModule::set_validator_metadata(RuntimeOrigin::signed(1), b"alice".to_vec(), vec![], vec![]),
+			Error::<Test>::NotValidator
+		);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::set_validator_metadata(
+			RuntimeOrigin::signed(1),
+			b"alice".to_vec(),
+			b"https://alice.example".to_vec(),
+			b"5% commission".to_vec(),
+		));
+		System::assert_last_event(Event::MetadataUpdated(1).into());
+		let info = TemplateModule::validator_metadata(1).unwrap();
+		assert_eq!(info.name.to_vec(), b"alice".to_vec());
+		assert_eq!(info.website.to_vec(), b"https://alice.example".to_vec());
+		assert_eq!(info.description.to_vec(), b"5% commission".to_vec());
+		
+		// A second call replaces every field
+		assert_ok!(TemplateModule::set_validator_metadata(RuntimeOrigin::signed(1), b"al".to_vec(), vec![], vec![]));
+		let info = TemplateModule::validator_metadata(1).unwrap();
+		assert_eq!(info.name.to_vec(), b"al".to_vec());
+		assert!(info.website.is_empty());
+		assert!(info.description.is_empty());
+		
+		// Leaving the validator set drops the metadata
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		assert_eq!(TemplateModule::validator_metadata(1), None);
+	});
+}
+
+#[test]
+fn validator_metadata_rejects_oversized_fields() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		for (name, website, description) in [
+			(vec![b'a'; 65], vec![], vec![]),
+			(vec![], vec![b'a'; 129], vec![]),
+			(vec![], vec![], vec![b'a'; 513]),
+		] {
+			assert_noop!(
+				TemplateModule::set_validator_metadata(RuntimeOrigin::signed(1), name, website, description),
+				Error::<Test>::MetadataFieldTooLong
+			);
+		}
+		
+		// Fields exactly at their bound are accepted
+		assert_ok!(TemplateModule::set_validator_metadata(
+			RuntimeOrigin::signed(1),
+			vec![b'a'; 64],
+			vec![b'a'; 128],
+			vec![b'a'; 512],
+		));
+	});
+}
+
+#[test]
+fn nominate_works() {
+	new_test_ext().execute_with(|| {
+		// Register validator
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		// Nominate validator
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// Check nomination is stored
+		let nominations = TemplateModule::nominators(2);
+		assert_eq!(nominations.len(), 1);
+		assert_eq!(nominations[0].validator, 1);
+		assert_eq!(nominations[0].amount, 100);
+		
+		// Check total validator stake is updated
+		assert_eq!(TemplateModule::total_validator_stake(1), 600);
+		
+		// Check total staked is updated
+		assert_eq!(TemplateModule::total_staked(), 600);
+		
+		// System emits event
+		System::assert_has_event(Event::Nomination(2, 1, 100).into());
+	});
+}
+
+#[test]
+fn nominate_fails_when_validator_not_exists() {
+	new_test_ext().execute_with(|| {
+		// Try to nominate non-existent validator
+		assert_noop!(
+			TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100),
+			Error::<Test>::NotValidator
+		);
+	});
+}
+
+#[test]
+fn nominate_fails_with_insufficient_amount() {
+	new_test_ext().execute_with(|| {
+		// Register validator
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		// Try to nominate with less than minimum
+		assert_noop!(
+			TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 5),
+			Error::<Test>::NominationBelowMinimum
+		);
+	});
+}
+
+#[test]
+fn nominate_requires_active_validator_when_configured() {
+	new_test_ext().execute_with(|| {
+		RequireActiveValidatorForNomination::set(true);
+		
+		// Validators 1 and 2 are elected, validator 3 is registered but outside the active set
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(2), 400));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(3), 300));
+		run_to_next_era();
+		assert_eq!(TemplateModule::active_set().to_vec(), vec![1, 2]);
+		
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(4), 1, 100));
+		assert_noop!(
+			TemplateModule::nominate(RuntimeOrigin::signed(4), 3, 100),
+			Error::<Test>::ValidatorNotActive
+		);
+		
+		// Without the flag inactive validators take nominations
+		RequireActiveValidatorForNomination::set(false);
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(4), 3, 100));
+	});
+}
+
+#[test]
+fn nominate_fails_when_validator_is_oversubscribed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		let cap = MaxNominatorsPerValidator::get() as u64;
+		for nominator in 100..100 + cap {
+			Balances::make_free_balance_be(&nominator, 1_000);
+			assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(nominator), 1, 100));
+		}
+		assert_eq!(TemplateModule::backer_count(&1), cap as u32);
+		
+		Balances::make_free_balance_be(&200, 1_000);
+		assert_noop!(
+			TemplateModule::nominate(RuntimeOrigin::signed(200), 1, 100),
+			Error::<Test>::ValidatorOversubscribed
+		);
+		
+		// A withdrawal frees a slot for the next nominator
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(100), 1));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(200), 1, 100));
+	});
+}
+
+#[test]
+fn nominate_fails_when_already_nominated() {
+	new_test_ext().execute_with(|| {
+		// Register validator
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		// Nominate validator
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// Try to nominate again
+		assert_noop!(
+			TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100),
+			Error::<Test>::AlreadyNominated
+		);
+	});
+}
+
+#[test]
+fn withdraw_nomination_works() {
+	new_test_ext().execute_with(|| {
+		// Register validator
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		// Nominate validator
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// Withdraw nomination
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		
+		// Check nomination is removed
+		let nominations = TemplateModule::nominators(2);
+		assert_eq!(nominations.len(), 0);
+		
+		// Check total validator stake is updated
+		assert_eq!(TemplateModule::total_validator_stake(1), 500);
+		
+		// Check total staked is updated
+		assert_eq!(TemplateModule::total_staked(), 500);
+		
+		// System emits event
+		System::assert_has_event(Event::NominationWithdrawn(2, 1, 100).into());
+	});
+}
+
+#[test]
+fn withdraw_nomination_only_unreserves_what_is_still_reserved() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// The nominator loses part of its reserve outside of the pallet
+		let (_, unslashed) = Balances::slash_reserved(&2, 40);
+		assert_eq!(unslashed, 0);
+		assert_eq!(Balances::reserved_balance(2), 60);
+		
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		System::assert_has_event(Event::NominationWithdrawn(2, 1, 60).into());
+		
+		// Only the 60 left in reserve comes back, nothing is freed out of thin air
+		run_to_next_era();
+		run_to_next_era();
+		assert_ok!(TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(2)));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 960);
+	});
+}
+
+#[test]
+fn unbonded_stake_is_withdrawable_after_unbonding_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// Removing the validator starts unbonding instead of unreserving
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		System::assert_has_event(Event::Unbonded(1, 500, 2).into());
+		assert_eq!(Balances::reserved_balance(1), 500);
+		assert_eq!(TemplateModule::unbonding(1).to_vec(), vec![(500, 2)]);
+		
+		// Too early to withdraw
+		assert_noop!(
+			TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(1)),
+			Error::<Test>::NothingUnlocked
+		);
+		
+		run_to_next_era();
+		assert_noop!(
+			TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(1)),
+			Error::<Test>::NothingUnlocked
+		);
+		
+		run_to_next_era();
+		assert_ok!(TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::Withdrawn(1, 500).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(TemplateModule::unbonding(1).is_empty());
+	});
+}
+
+#[test]
+fn withdraw_unbonded_only_releases_unlocked_chunks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(3), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 3, 50));
+		
+		// Two chunks unlocking one era apart
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		run_to_next_era();
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 3));
+		assert_eq!(TemplateModule::unbonding(2).to_vec(), vec![(100, 2), (50, 3)]);
+		
+		// Only the first chunk is released at era 2
+		run_to_next_era();
+		assert_ok!(TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(2)));
+		System::assert_last_event(Event::Withdrawn(2, 100).into());
+		assert_eq!(Balances::reserved_balance(2), 50);
+		assert_eq!(TemplateModule::unbonding(2).to_vec(), vec![(50, 3)]);
+		
+		run_to_next_era();
+		assert_ok!(TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(2)));
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn has_claimable_reports_rewards_and_matured_unbonding() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		
+		// Nothing yet: no rewards and the unbonding chunk has not matured
+		assert!(!TemplateModule::has_claimable(&10));
+		assert!(!TemplateModule::has_claimable(&1));
+		assert!(!TemplateModule::has_claimable(&2));
+		
+		run_to_next_era();
+		assert!(TemplateModule::has_claimable(&10));
+		assert!(!TemplateModule::has_claimable(&1));
+		
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(1), 0);
+		assert!(TemplateModule::has_claimable(&1));
+		assert!(!TemplateModule::has_claimable(&2));
+	});
+}
+
+#[test]
+fn sub_existential_withdrawal_is_refused_to_keep_account_alive() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(100);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 50));
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		
+		// Another provider lets the account spend its free balance below the existential deposit
+		System::inc_providers(&2);
+		Balances::make_free_balance_be(&2, 20);
+		run_to_next_era();
+		run_to_next_era();
+		
+		assert_noop!(
+			TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(2)),
+			Error::<Test>::WithdrawalWouldReap
+		);
+		assert_eq!(Balances::reserved_balance(2), 50);
+		
+		// Once topped up the account survives the withdrawal
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(2)));
+		System::assert_last_event(Event::Withdrawn(2, 50).into());
+		assert_eq!(Balances::free_balance(2), 150);
+	});
+}
+
+#[test]
+fn sub_existential_withdrawal_goes_to_dust_collector() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(100);
+		DustHandling::set(DustPolicy::Collect(TreasuryAccount::get()));
+		Balances::make_free_balance_be(&TreasuryAccount::get(), 1_000);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 50));
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		System::inc_providers(&2);
+		Balances::make_free_balance_be(&2, 20);
+		run_to_next_era();
+		run_to_next_era();
+		
+		// The remainder and the free dust are collected rather than burned
+		assert_ok!(TemplateModule::withdraw_unbonded(RuntimeOrigin::signed(2)));
+		System::assert_has_event(Event::DustCollected(2, TreasuryAccount::get(), 70).into());
+		System::assert_last_event(Event::Withdrawn(2, 0).into());
+		assert_eq!(Balances::total_balance(&2), 0);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 1_070);
+	});
+}
+
+#[test]
+fn force_unbond_all_releases_chunks_before_maturity() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(3), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 3, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 50));
+		
+		// A validator and its nominator with chunks that have not matured
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		assert_eq!(TemplateModule::unbonding(1).to_vec(), vec![(500, 2)]);
+		assert_eq!(TemplateModule::unbonding(2).to_vec(), vec![(50, 2)]);
+		
+		assert_noop!(
+			TemplateModule::force_unbond_all(RuntimeOrigin::signed(1), 10),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		
+		assert_ok!(TemplateModule::force_unbond_all(RuntimeOrigin::root(), 10));
+		System::assert_has_event(Event::EmergencyUnbonded(1, 500).into());
+		System::assert_has_event(Event::EmergencyUnbonded(2, 50).into());
+		assert!(TemplateModule::unbonding(1).is_empty());
+		assert!(TemplateModule::unbonding(2).is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		// The still active nomination stays reserved
+		assert_eq!(Balances::reserved_balance(2), 100);
+	});
+}
+
+#[test]
+fn force_unbond_all_pages_through_accounts() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		for validator in [1, 2, 3] {
+			assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), 500));
+			assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(validator)));
+		}
+		
+		// Each page releases at most `limit` accounts and remembers where it stopped
+		assert_ok!(TemplateModule::force_unbond_all(RuntimeOrigin::root(), 2));
+		assert_eq!(crate::Unbonding::<Test>::iter().count(), 1);
+		assert!(TemplateModule::force_unbond_cursor().is_some());
+		
+		// An empty page is rejected rather than losing the cursor
+		assert_noop!(
+			TemplateModule::force_unbond_all(RuntimeOrigin::root(), 0),
+			Error::<Test>::ZeroLimit
+		);
+		assert!(TemplateModule::force_unbond_cursor().is_some());
+		
+		// The next page carries on after the cursor and finishes the sweep
+		assert_ok!(TemplateModule::force_unbond_all(RuntimeOrigin::root(), 2));
+		assert_eq!(crate::Unbonding::<Test>::iter().count(), 0);
+		assert_eq!(TemplateModule::force_unbond_cursor(), None);
+		for validator in [1, 2, 3] {
+			System::assert_has_event(Event::EmergencyUnbonded(validator, 500).into());
+			assert_eq!(Balances::reserved_balance(validator), 0);
+		}
+	});
+}
+
+#[test]
+fn force_unbond_all_resumes_after_kept_accounts() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		for validator in [1, 2] {
+			assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), 500));
+			assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(validator)));
+		}
+		ExistentialDeposit::set(1_000);
+		
+		// Whichever account comes first would be reaped, so it keeps its chunks
+		let (first, _) = crate::Unbonding::<Test>::iter().next().unwrap();
+		let second = if first == 1 { 2 } else { 1 };
+		System::inc_providers(&first);
+		Balances::make_free_balance_be(&first, 100);
+		
+		assert_ok!(TemplateModule::force_unbond_all(RuntimeOrigin::root(), 1));
+		assert!(!TemplateModule::unbonding(first).is_empty());
+		assert_eq!(TemplateModule::force_unbond_cursor(), Some(first));
+		
+		// The next page moves past it instead of retrying it
+		assert_ok!(TemplateModule::force_unbond_all(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::EmergencyUnbonded(second, 500).into());
+		assert!(TemplateModule::unbonding(second).is_empty());
+	});
+}
+
+#[test]
+fn nominator_count_tracks_first_and_last_nomination() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(2), 500));
+		
+		// First nomination of a new account
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 1, 100));
+		assert_eq!(TemplateModule::nominator_count(), 1);
+		
+		// A second nomination by the same account
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 2, 100));
+		assert_eq!(TemplateModule::nominator_count(), 1);
+		
+		// Withdrawing all of them
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(3), 1));
+		assert_eq!(TemplateModule::nominator_count(), 1);
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(3), 2));
+		assert_eq!(TemplateModule::nominator_count(), 0);
+		assert!(!crate::Nominators::<Test>::contains_key(3));
+	});
+}
+
+#[test]
+fn nominators_by_validator_follows_nominations() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(2), 500));
+		
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 1, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(4), 1, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 2, 100));
+		assert_eq!(TemplateModule::nominators_by_validator(1).to_vec(), vec![3, 4]);
+		assert_eq!(TemplateModule::nominators_by_validator(2).to_vec(), vec![3]);
+		assert_eq!(TemplateModule::backer_count(&1), 2);
+		
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(3), 1));
+		assert_eq!(TemplateModule::nominators_by_validator(1).to_vec(), vec![4]);
+		assert_eq!(TemplateModule::nominators_by_validator(2).to_vec(), vec![3]);
+		
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(3), 2));
+		assert!(!crate::NominatorsByValidator::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn withdraw_nomination_fails_when_not_nominated() {
+	new_test_ext().execute_with(|| {
+		// Register validator
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		// Try to withdraw non-existent nomination
+		assert_noop!(
+			TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::NominationNotFound
+		);
+	});
+}
+
+#[test]
+fn increase_nomination_reserves_more() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		assert_ok!(TemplateModule::increase_nomination(RuntimeOrigin::signed(2), 1, 50));
+		
+		assert_eq!(TemplateModule::nominators(2)[0].amount, 150);
+		assert_eq!(Balances::reserved_balance(2), 150);
+		assert_eq!(TemplateModule::total_validator_stake(1), 650);
+		assert_eq!(TemplateModule::total_staked(), 650);
+		System::assert_last_event(Event::NominationIncreased(2, 1, 150).into());
+		
+		assert_noop!(
+			TemplateModule::increase_nomination(RuntimeOrigin::signed(2), 1, 10_000),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			TemplateModule::increase_nomination(RuntimeOrigin::signed(3), 1, 50),
+			Error::<Test>::NominationNotFound
+		);
+	});
+}
+
+#[test]
+fn increase_nomination_requires_a_nominatable_validator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// No topping up behind a validator under investigation
+		assert_ok!(TemplateModule::report_misbehavior(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			TemplateModule::increase_nomination(RuntimeOrigin::signed(2), 1, 50),
+			Error::<Test>::ValidatorUnderInvestigation
+		);
+		assert_ok!(TemplateModule::resolve_report(RuntimeOrigin::root(), 1));
+		
+		// Nor behind one that has left
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			TemplateModule::increase_nomination(RuntimeOrigin::signed(2), 1, 50),
+			Error::<Test>::NotValidator
+		);
+		assert_eq!(TemplateModule::total_validator_stake(1), 0);
+	});
+}
+
+#[test]
+fn decrease_nomination_restarts_the_bonding_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_eq!(TemplateModule::bonded_since_era(2), Some(0));
+		run_to_next_era();
+		run_to_next_era();
+		
+		assert_ok!(TemplateModule::decrease_nomination(RuntimeOrigin::signed(2), 1, 50));
+		assert_eq!(TemplateModule::bonded_since_era(2), Some(2));
+	});
+}
+
+#[test]
+fn decrease_nomination_down_to_minimum_unbonds_the_difference() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// Exactly `MinNomination` is left
+		assert_ok!(TemplateModule::decrease_nomination(RuntimeOrigin::signed(2), 1, 90));
+		
+		assert_eq!(TemplateModule::nominators(2)[0].amount, 10);
+		assert_eq!(TemplateModule::total_validator_stake(1), 510);
+		assert_eq!(TemplateModule::total_staked(), 510);
+		System::assert_last_event(Event::NominationDecreased(2, 1, 10).into());
+		
+		// The difference stays reserved until the unbonding period is over
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert_eq!(TemplateModule::unbonding(2).into_inner(), vec![(90, UnbondingPeriod::get())]);
+	});
+}
+
+#[test]
+fn decrease_nomination_fails_below_minimum() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		assert_noop!(
+			TemplateModule::decrease_nomination(RuntimeOrigin::signed(2), 1, 91),
+			Error::<Test>::NominationBelowMinimum
+		);
+		assert_noop!(
+			TemplateModule::decrease_nomination(RuntimeOrigin::signed(2), 1, 200),
+			Error::<Test>::NominationBelowMinimum
+		);
+	});
+}
+
+#[test]
+fn re_nominate_moves_stake_without_unbonding() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(3), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		assert_ok!(TemplateModule::re_nominate(RuntimeOrigin::signed(2), 1, 3));
+		
+		// The nomination now backs validator 3, still reserved and not unbonding
+		let nominations = TemplateModule::nominators(2);
+		assert_eq!(nominations.len(), 1);
+		assert_eq!(nominations[0].validator, 3);
+		assert_eq!(nominations[0].amount, 100);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert!(TemplateModule::unbonding(2).is_empty());
+		
+		assert_eq!(TemplateModule::total_validator_stake(1), 500);
+		assert_eq!(TemplateModule::total_validator_stake(3), 600);
+		assert_eq!(TemplateModule::total_staked(), 1_100);
+		assert!(TemplateModule::nominators_by_validator(1).is_empty());
+		assert_eq!(TemplateModule::nominators_by_validator(3).into_inner(), vec![2]);
+		
+		System::assert_has_event(Event::NominationWithdrawn(2, 1, 100).into());
+		System::assert_last_event(Event::Nomination(2, 3, 100).into());
+	});
+}
+
+#[test]
+fn re_nominate_fails_to_nonexistent_validator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		assert_noop!(
+			TemplateModule::re_nominate(RuntimeOrigin::signed(2), 1, 3),
+			Error::<Test>::NotValidator
+		);
+		
+		// Moving a nomination the account does not hold fails too
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(3), 500));
+		assert_noop!(
+			TemplateModule::re_nominate(RuntimeOrigin::signed(2), 3, 1),
+			Error::<Test>::NominationNotFound
+		);
+	});
+}
+
+#[test]
+fn nominator_over_lowered_limit_prunes_smallest_nominations() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		for validator in [1, 2, 3, 4, 5] {
+			assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), 500));
+		}
+		for (validator, amount) in [(1, 100), (3, 40), (4, 70), (5, 30)] {
+			assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(10), validator, amount));
+		}
+		
+		assert_noop!(
+			TemplateModule::set_nomination_limit(RuntimeOrigin::signed(10), 2),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::set_nomination_limit(RuntimeOrigin::root(), MaxNominations::get() + 1),
+			Error::<Test>::NominationLimitTooHigh
+		);
+		assert_ok!(TemplateModule::set_nomination_limit(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::NominationLimitSet(2).into());
+		assert_eq!(TemplateModule::max_nominations(), 2);
+		
+		// Over the limit, no new nomination is accepted
+		assert_noop!(
+			TemplateModule::nominate(RuntimeOrigin::signed(10), 2, 100),
+			Error::<Test>::MaxNominationsReached
+		);
+		
+		// The two smallest nominations are withdrawn
+		assert_ok!(TemplateModule::prune_excess_nominations(RuntimeOrigin::signed(10)));
+		System::assert_has_event(Event::NominationWithdrawn(10, 5, 30).into());
+		System::assert_has_event(Event::NominationWithdrawn(10, 3, 40).into());
+		let mut remaining: Vec<u64> = TemplateModule::nominators(10).iter().map(|n| n.validator).collect();
+		remaining.sort();
+		assert_eq!(remaining, vec![1, 4]);
+		assert_eq!(TemplateModule::total_nominated(&10), 170);
+		assert_eq!(TemplateModule::total_validator_stake(3), 500);
+		assert_eq!(TemplateModule::total_validator_stake(5), 500);
+		
+		assert_noop!(
+			TemplateModule::prune_excess_nominations(RuntimeOrigin::signed(10)),
+			Error::<Test>::WithinNominationLimit
+		);
+	});
+}
+
+#[test]
+fn withdrawal_is_held_while_validator_under_investigation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		
+		// Report the validator
+		assert_ok!(TemplateModule::report_misbehavior(RuntimeOrigin::root(), 1));
+		assert!(TemplateModule::under_investigation(1));
+		System::assert_last_event(Event::MisbehaviorReported(1).into());
+		
+		// The nomination is withdrawn but the funds stay reserved
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::WithdrawalHeld(2, 1, 100).into());
+		assert!(TemplateModule::nominators(2).is_empty());
+		assert_eq!(TemplateModule::total_validator_stake(1), 500);
+		assert_eq!(TemplateModule::total_staked(), 500);
+		assert_eq!(TemplateModule::held_withdrawals(1, 2), 100);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		
+		// Resolving the report releases the held funds
+		assert_ok!(TemplateModule::resolve_report(RuntimeOrigin::root(), 1));
+		System::assert_has_event(Event::NominationWithdrawn(2, 1, 100).into());
+		System::assert_last_event(Event::ReportResolved(1).into());
+		assert!(!TemplateModule::under_investigation(1));
+		assert_eq!(TemplateModule::held_withdrawals(1, 2), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn held_withdrawal_is_slashed_with_the_validator() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_ok!(TemplateModule::report_misbehavior(RuntimeOrigin::root(), 1));
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		
+		// Withdrawing during the investigation does not escape the slash
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Malice));
+		System::assert_has_event(Event::NominatorSlashed(2, 1, 20).into());
+		assert_eq!(TemplateModule::held_withdrawals(1, 2), 80);
+		assert_eq!(Balances::reserved_balance(2), 80);
+		
+		// Only the rest is released
+		assert_ok!(TemplateModule::resolve_report(RuntimeOrigin::root(), 1));
+		System::assert_has_event(Event::NominationWithdrawn(2, 1, 80).into());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 980);
+	});
+}
+
+#[test]
+fn held_withdrawal_unbonds_past_full_chunks_on_resolution() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_ok!(TemplateModule::report_misbehavior(RuntimeOrigin::root(), 1));
+		assert_ok!(TemplateModule::withdraw_nomination(RuntimeOrigin::signed(2), 1));
+		
+		// Releasing the hold would reap the nominator, and its unbonding chunks are full
+		let chunks = vec![(10, 5), (10, 6), (10, 7), (10, 8)];
+		crate::Unbonding::<Test>::insert(2, BoundedVec::truncate_from(chunks));
+		ExistentialDeposit::set(1_000);
+		System::inc_providers(&2);
+		Balances::make_free_balance_be(&2, 100);
+		
+		// The report still resolves, unbonding the hold into the newest chunk
+		assert_ok!(TemplateModule::resolve_report(RuntimeOrigin::root(), 1));
+		assert_eq!(TemplateModule::unbonding(2).to_vec(), vec![(10, 5), (10, 6), (10, 7), (110, 8)]);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert!(!TemplateModule::under_investigation(1));
+	});
+}
+
+#[test]
+fn report_misbehavior_checks_state() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::report_misbehavior(RuntimeOrigin::root(), 1),
+			Error::<Test>::NotValidator
+		);
+		assert_noop!(
+			TemplateModule::resolve_report(RuntimeOrigin::root(), 1),
+			Error::<Test>::NotUnderInvestigation
+		);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_noop!(
+			TemplateModule::report_misbehavior(RuntimeOrigin::signed(2), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::report_misbehavior(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			TemplateModule::report_misbehavior(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyUnderInvestigation
+		);
+	});
+}
+
+#[test]
+fn rewards_distribution_works() {
+	new_test_ext().execute_with(|| {
+		// Register validator
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		// Nominate validator
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		
+		// Advance blocks to trigger reward distribution
+		run_to_next_era();
+		
+		// Check rewards were distributed
+		assert!(TemplateModule::pending_rewards(10) > 0);
+		assert!(TemplateModule::pending_rewards(11) > 0);
+		
+		// Claim rewards for validator
+		let validator_rewards = TemplateModule::pending_rewards(10);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		
+		// Check rewards were claimed
+		assert_eq!(TemplateModule::pending_rewards(10), 0);
+		
+		// Claim rewards for nominator
+		let nominator_rewards = TemplateModule::pending_rewards(11);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(11)));
+		
+		// Check rewards were claimed
+		assert_eq!(TemplateModule::pending_rewards(11), 0);
+		
+		// System emits events
+		System::assert_has_event(Event::RewardsClaimed(10, validator_rewards).into());
+		System::assert_has_event(Event::RewardsClaimed(11, nominator_rewards).into());
+	});
+}
+
+#[test]
+fn new_era_event_precedes_distribution() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		// Nothing happens before the boundary
+		TemplateModule::on_initialize(EraDuration::get() - 1);
+		assert_eq!(TemplateModule::current_era(), 0);
+		
+		System::set_block_number(EraDuration::get());
+		TemplateModule::on_initialize(EraDuration::get());
+		
+		let events: Vec<RuntimeEvent> = System::events().into_iter().map(|record| record.event).collect();
+		let position = |event: Event<Test>| {
+			let event = RuntimeEvent::from(event);
+			events.iter().position(|e| *e == event)
+		};
+		let new_era = position(Event::NewEra(1, EraDuration::get())).expect("NewEra is emitted");
+		let distributed = position(Event::RewardsDistributed(0, 403_200)).expect("rewards are distributed");
+		assert!(new_era < distributed);
+	});
+}
+
+#[test]
+fn force_new_era_rotates_and_distributes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(100);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		assert_noop!(
+			TemplateModule::force_new_era(RuntimeOrigin::signed(10)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		
+		assert_ok!(TemplateModule::force_new_era(RuntimeOrigin::root()));
+		assert_eq!(TemplateModule::current_era(), 1);
+		assert_eq!(TemplateModule::era_start_block(), 100);
+		System::assert_has_event(Event::NewEra(1, 100).into());
+		System::assert_has_event(Event::RewardsDistributed(0, 403_200).into());
+		assert_eq!(TemplateModule::pending_rewards(10), 403_200);
+		
+		// Forcing again in the same block does nothing
+		let events = System::events().len();
+		assert_ok!(TemplateModule::force_new_era(RuntimeOrigin::root()));
+		assert_eq!(TemplateModule::current_era(), 1);
+		assert_eq!(System::events().len(), events);
+		
+		// The next automatic rotation counts from the forced start
+		TemplateModule::on_initialize(EraDuration::get());
+		assert_eq!(TemplateModule::current_era(), 1);
+		run_to_next_era();
+		assert_eq!(TemplateModule::current_era(), 2);
+	});
+}
+
+#[test]
+fn treasury_takes_its_cut_before_stakers() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TreasuryCut::set(Perbill::from_percent(10));
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_eq!(TemplateModule::projected_era_reward(&10), 362_880);
+		run_to_next_era_conserving();
+		
+		// 10% of the 403_200 pool goes to the treasury, the validator keeps the other 90%
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 40_320);
+		assert_eq!(TemplateModule::pending_rewards(10), 362_880);
+		System::assert_has_event(Event::TreasuryFunded(0, 40_320).into());
+		System::assert_has_event(Event::RewardsDistributed(0, 362_880).into());
+		
+		// The whole minted pool counts towards the growth clamp
+		assert_eq!(TemplateModule::prev_era_pool(), 403_200);
+	});
+}
+
+#[test]
+fn eras_rotate_at_the_configured_duration() {
+	new_test_ext().execute_with(|| {
+		EraDuration::set(10);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		TemplateModule::on_initialize(9);
+		assert_eq!(TemplateModule::current_era(), 0);
+		
+		System::set_block_number(10);
+		TemplateModule::on_initialize(10);
+		assert_eq!(TemplateModule::current_era(), 1);
+		
+		// Rewards scale with the era length: 28 per block on a stake of 1_000_000_000
+		System::assert_has_event(Event::RewardsDistributed(0, 280).into());
+		
+		TemplateModule::on_initialize(19);
+		assert_eq!(TemplateModule::current_era(), 1);
+		TemplateModule::on_initialize(20);
+		assert_eq!(TemplateModule::current_era(), 2);
+	});
+}
+
+#[test]
+fn reward_pool_growth_is_clamped() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		run_to_next_era_conserving();
+		
+		// First era pays the unclamped pool
+		let first_pool = TemplateModule::prev_era_pool();
+		assert_eq!(first_pool, 403_200);
+		
+		// Stake doubles, but the pool may only grow by 10%
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		run_to_next_era_conserving();
+		
+		let second_pool = TemplateModule::prev_era_pool();
+		assert_eq!(second_pool, 443_520);
+		System::assert_last_event(Event::RewardsDistributed(1, second_pool).into());
+		
+		// Both validators share the clamped pool equally
+		assert_eq!(TemplateModule::pending_rewards(10), first_pool + second_pool / 2);
+		assert_eq!(TemplateModule::pending_rewards(11), second_pool / 2);
+	});
+}
+
+#[test]
+fn projected_era_reward_follows_the_clamped_pool() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		run_to_next_era_conserving();
+		
+		// Doubling the stake would double the pool, but it may only grow by 10%
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		let projected = TemplateModule::projected_era_reward(&11);
+		assert_eq!(projected, 443_520 / 2);
+		
+		run_to_next_era_conserving();
+		assert_eq!(TemplateModule::pending_rewards(11), projected);
+	});
+}
+
+#[test]
+fn clamping_residual_is_paid_to_treasury() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		run_to_next_era_conserving();
+		assert_eq!(TemplateModule::prev_era_pool(), 403_200);
+		
+		// Three equal validators: scaling each reward down to a third of 443_520 rounds
+		// every share down and leaves a residual
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(12), 1_000_000_000));
+		run_to_next_era_conserving();
+		
+		// The whole clamped pool is minted, what rounding left over going to the treasury
+		let distributed = TemplateModule::era_total_rewards(1);
+		let residual = 443_520 - distributed;
+		assert!(residual > 0);
+		assert_eq!(TemplateModule::prev_era_pool(), 443_520);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), residual);
+		System::assert_has_event(Event::TreasuryFunded(1, residual).into());
+		System::assert_has_event(Event::RewardResidual(1, residual).into());
+		System::assert_last_event(Event::RewardsDistributed(1, distributed).into());
+	});
+}
+
+#[test]
+fn unclamped_residual_is_paid_to_treasury() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TreasuryCut::set(Perbill::from_percent(10));
+		
+		// A nomination that does not divide the pool evenly leaves rounding dust once the
+		// stakers' shares are cut down to 90%
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 333_333_333));
+		run_to_next_era_conserving();
+		
+		let minted = TemplateModule::prev_era_pool();
+		let distributed = TemplateModule::era_total_rewards(0);
+		let cut = Perbill::from_percent(10).mul_floor(minted);
+		let residual = minted - cut - distributed;
+		assert!(residual > 0);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), cut + residual);
+		System::assert_has_event(Event::TreasuryFunded(0, cut + residual).into());
+		System::assert_has_event(Event::RewardResidual(0, residual).into());
+	});
+}
+
+#[test]
+fn reward_history_is_kept_for_history_depth() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		
+		for _ in 0..3 {
+			run_to_next_era_conserving();
+		}
+		
+		// Every distributed era is recorded per account and in total
+		for era in 0..3 {
+			let validator = TemplateModule::era_rewards(era, 10);
+			let nominator = TemplateModule::era_rewards(era, 11);
+			assert!(validator > 0 && nominator > 0);
+			assert_eq!(TemplateModule::era_total_rewards(era), validator + nominator);
+		}
+		
+		// Only the last three eras are kept
+		run_to_next_era_conserving();
+		assert_eq!(TemplateModule::era_rewards(0, 10), 0);
+		assert_eq!(TemplateModule::era_total_rewards(0), 0);
+		assert!(TemplateModule::era_total_rewards(3) > 0);
+		assert!(TemplateModule::era_total_rewards(1) > 0);
+	});
+}
+
+#[test]
+fn projected_era_reward_matches_credited_reward() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		
+		let projected_validator = TemplateModule::projected_era_reward(&10);
+		let projected_nominator = TemplateModule::projected_era_reward(&11);
+		assert!(projected_validator > 0 && projected_nominator > 0);
+		
+		run_to_next_era();
+		
+		assert_eq!(TemplateModule::pending_rewards(10), projected_validator);
+		assert_eq!(TemplateModule::pending_rewards(11), projected_nominator);
+	});
+}
+
+#[test]
+fn simulated_era_rewards_match_distribution() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(12), 2_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 12, 1_000_000_000));
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(10), Perbill::from_percent(10)));
+		
+		let simulated = TemplateModule::simulate_era_rewards();
+		assert_eq!(simulated.len(), 3);
+		// Simulating writes nothing
+		assert_eq!(TemplateModule::pending_rewards(11), 0);
+		
+		run_to_next_era();
+		
+		for (account, amount) in simulated {
+			assert_eq!(TemplateModule::era_rewards(0, account), amount);
+		}
+	});
+}
+
+#[test]
+fn rewards_start_at_configured_era() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RewardStartEra::set(2);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		// Eras 0 and 1 pay nothing
+		run_to_next_era();
+		System::assert_last_event(Event::RewardsNotYetStarted(0).into());
+		run_to_next_era();
+		System::assert_last_event(Event::RewardsNotYetStarted(1).into());
+		assert_eq!(TemplateModule::pending_rewards(10), 0);
+		
+		// Era 2 pays normally
+		run_to_next_era();
+		System::assert_last_event(Event::RewardsDistributed(2, 403_200).into());
+		assert_eq!(TemplateModule::pending_rewards(10), 403_200);
+	});
+}
+
+#[test]
+fn rewards_accrue_but_cannot_be_claimed_while_minting_paused() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::set_minting_paused(RuntimeOrigin::root(), true));
+		System::assert_last_event(Event::MintingPausedSet(true).into());
+		
+		// Rewards still accrue
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(10), 403_200);
+		
+		// But nothing is minted
+		assert_noop!(
+			TemplateModule::claim_rewards(RuntimeOrigin::signed(10)),
+			Error::<Test>::MintingPaused
+		);
+		assert_noop!(
+			TemplateModule::claim_and_restake(RuntimeOrigin::signed(10), 10),
+			Error::<Test>::MintingPaused
+		);
+		
+		// Claiming works again once resumed
+		assert_ok!(TemplateModule::set_minting_paused(RuntimeOrigin::root(), false));
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		System::assert_last_event(Event::RewardsClaimed(10, 403_200).into());
+		assert_eq!(TemplateModule::pending_rewards(10), 0);
+	});
+}
+
+#[test]
+fn treasury_cut_is_held_while_minting_paused() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		TreasuryCut::set(Perbill::from_percent(10));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::set_minting_paused(RuntimeOrigin::root(), true));
+		
+		// The cut is recorded but not minted
+		run_to_next_era();
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 0);
+		assert_eq!(TemplateModule::pending_treasury_cut(), 40_320);
+		
+		// Resuming mints what was held back
+		assert_ok!(TemplateModule::set_minting_paused(RuntimeOrigin::root(), false));
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 40_320);
+		assert_eq!(TemplateModule::pending_treasury_cut(), 0);
+		System::assert_last_event(Event::TreasuryFunded(1, 40_320).into());
+	});
+}
+
+#[test]
+fn only_active_set_earns_rewards_when_configured() {
+	new_test_ext().execute_with(|| {
+		RewardActiveSetOnly::set(true);
+		
+		// Three validators compete for two active slots
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 3_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 2_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(12), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(13), 12, 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(13), 11, 1_000_000_000));
+		
+		// Nobody is active before the first election
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(10), 0);
+		assert_eq!(TemplateModule::active_set().to_vec(), vec![10, 11]);
+		
+		// Only the elected validators and their nominators are paid
+		run_to_next_era();
+		assert!(TemplateModule::pending_rewards(10) > 0);
+		assert!(TemplateModule::pending_rewards(11) > 0);
+		assert_eq!(TemplateModule::pending_rewards(12), 0);
+		assert_eq!(
+			TemplateModule::pending_rewards(13),
+			TemplateModule::projected_era_reward(&13)
+		);
+	});
+}
+
+#[test]
+fn effective_apr_accounts_for_commission() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(2), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 1, 100));
+		
+		// Without commission the rate blends 15% on self stake with 10% on nominations
+		let pool_apr_1 = Perbill::from_rational(15 * 500 + 10 * 100u32, 100 * 600u32);
+		assert_eq!(TemplateModule::validator_effective_apr(&1), pool_apr_1);
+		assert_eq!(TemplateModule::validator_effective_apr(&2), Perbill::from_percent(15));
+		
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(1), Perbill::from_percent(10)));
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(2), Perbill::from_percent(50)));
+		System::assert_last_event(Event::CommissionSet(2, Perbill::from_percent(50)).into());
+		
+		assert_eq!(TemplateModule::validator_effective_apr(&1), Perbill::from_percent(90) * pool_apr_1);
+		assert_eq!(TemplateModule::validator_effective_apr(&2), Perbill::from_rational(75u32, 1000u32));
+		assert!(TemplateModule::validator_effective_apr(&2) < TemplateModule::validator_effective_apr(&1));
+		
+		// Not a validator
+		assert_eq!(TemplateModule::validator_effective_apr(&3), Perbill::zero());
+		assert_noop!(
+			TemplateModule::set_commission(RuntimeOrigin::signed(3), Perbill::from_percent(5)),
+			Error::<Test>::NotValidator
+		);
+	});
+}
+
+#[test]
+fn commission_is_taken_from_the_shared_reward_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(10), Perbill::from_percent(20)));
+		
+		run_to_next_era_conserving();
+		
+		// Pool of 403_200 (own stake) + 273_600 (nomination): 20% commission, the rest split
+		// evenly by stake, leaving the validator above its 403_200 solo reward
+		let pool = 403_200 + 273_600;
+		let commission = Perbill::from_percent(20).mul_floor(pool);
+		let share = (pool - commission) / 2;
+		assert_eq!(TemplateModule::commission_earned(10), commission);
+		assert_eq!(TemplateModule::pending_rewards(10), share);
+		assert_eq!(TemplateModule::pending_rewards(11), share);
+		assert!(commission + share > 403_200);
+	});
+}
+
+#[test]
+fn commission_above_soft_cap_goes_to_nominators() {
+	new_test_ext().execute_with(|| {
+		SoftCommissionCap::set(Perbill::from_percent(10));
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(10), Perbill::from_percent(20)));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(12), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(13), 12, 1_000_000_000));
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(12), Perbill::from_percent(5)));
+		
+		run_to_next_era_conserving();
+		
+		// Validator 10 keeps the capped 10% and the other 10% goes to its only nominator
+		let pool = 403_200 + 273_600;
+		let capped = Perbill::from_percent(10).mul_floor(pool);
+		let share = (pool - 2 * capped) / 2;
+		assert_eq!(TemplateModule::commission_earned(10), capped);
+		assert_eq!(TemplateModule::pending_rewards(10), share);
+		assert_eq!(TemplateModule::pending_rewards(11), share + capped);
+		
+		// Validator 12 is below the cap and keeps all its commission
+		let commission = Perbill::from_percent(5).mul_floor(pool);
+		assert_eq!(TemplateModule::commission_earned(12), commission);
+		assert_eq!(TemplateModule::pending_rewards(13), (pool - commission) / 2);
+	});
+}
+
+#[test]
+fn randomized_stakes_conserve_rewards_every_era() {
+	for seed in 0..24 {
+		new_test_ext().execute_with(|| {
+			let mut rng = Lcg(seed);
+			TreasuryCut::set(Perbill::from_percent(rng.below(25) as u32));
+			MaxLoyaltyBonus::set(Perbill::from_percent(rng.below(20) as u32));
+			SoftCommissionCap::set(Perbill::from_percent(5 + rng.below(96) as u32));
+			RedirectExcessCommission::set(rng.below(2) == 0);
+			
+			let validators: Vec<u64> = (100..101 + rng.below(5)).collect();
+			for &validator in &validators {
+				Balances::make_free_balance_be(&validator, 1_000_000_000_000_000);
+				let stake = 100 + rng.below(50_000_000_000) as u128;
+				assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(validator), stake));
+				let commission = Perbill::from_percent(rng.below(101) as u32);
+				assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(validator), commission));
+			}
+			for nominator in 200..200 + rng.below(12) {
+				Balances::make_free_balance_be(&nominator, 1_000_000_000_000_000);
+				let validator = validators[rng.below(validators.len() as u64) as usize];
+				let amount = 10 + rng.below(50_000_000_000) as u128;
+				assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(nominator), validator, amount));
+				if rng.below(3) == 0 {
+					assert_ok!(TemplateModule::set_payee(RuntimeOrigin::signed(nominator), RewardDestination::Staked));
+				}
+			}
+			
+			for _ in 0..6 {
+				run_to_next_era_conserving();
+				
+				// Shift the stake between eras so the pool keeps moving against its growth ceiling
+				let validator = validators[rng.below(validators.len() as u64) as usize];
+				let extra = 1 + rng.below(20_000_000_000) as u128;
+				assert_ok!(TemplateModule::bond_extra(RuntimeOrigin::signed(validator), extra));
+			}
+		});
+	}
+}
+
+#[test]
+fn commission_above_soft_cap_is_not_minted_without_redirect() {
+	new_test_ext().execute_with(|| {
+		SoftCommissionCap::set(Perbill::from_percent(10));
+		RedirectExcessCommission::set(false);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(10), Perbill::from_percent(20)));
+		
+		run_to_next_era();
+		
+		let pool = 403_200 + 273_600;
+		let capped = Perbill::from_percent(10).mul_floor(pool);
+		let share = (pool - 2 * capped) / 2;
+		assert_eq!(TemplateModule::commission_earned(10), capped);
+		assert_eq!(TemplateModule::pending_rewards(10), share);
+		assert_eq!(TemplateModule::pending_rewards(11), share);
+	});
+}
+
+#[test]
+fn commission_is_claimed_separately_from_rewards() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::set_commission(RuntimeOrigin::signed(10), Perbill::from_percent(20)));
+		assert_noop!(TemplateModule::claim_commission(RuntimeOrigin::signed(10)), Error::<Test>::NoCommission);
+		
+		run_to_next_era();
+		
+		let commission = TemplateModule::commission_earned(10);
+		let rewards = TemplateModule::pending_rewards(10);
+		assert_eq!(commission, Perbill::from_percent(20).mul_floor(403_200 + 273_600u128));
+		
+		// Claiming commission leaves the staking rewards pending
+		let free = Balances::free_balance(10);
+		assert_ok!(TemplateModule::claim_commission(RuntimeOrigin::signed(10)));
+		System::assert_last_event(Event::CommissionClaimed(10, commission).into());
+		assert_eq!(Balances::free_balance(10), free + commission);
+		assert_eq!(TemplateModule::commission_earned(10), 0);
+		assert_eq!(TemplateModule::pending_rewards(10), rewards);
+		
+		// And claiming rewards leaves no commission behind
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		assert_eq!(Balances::free_balance(10), free + commission + rewards);
+		assert_noop!(TemplateModule::claim_commission(RuntimeOrigin::signed(10)), Error::<Test>::NoCommission);
+	});
+}
+
+#[test]
+fn nominators_share_the_validator_pool_pro_rata() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 2_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(12), 10, 3_000_000_000));
+		
+		run_to_next_era();
+		
+		// 820_800 for the self stake plus the nominator rate on the 4_000_000_000 nominated
+		let pool: u128 = 820_800 + 4_000_000_000 * 10 / 525_600_000 * 14_400;
+		let share_11 = Perbill::from_rational(1u32, 6u32).mul_floor(pool);
+		let share_12 = Perbill::from_rational(1u32, 2u32).mul_floor(pool);
+		assert_eq!(TemplateModule::pending_rewards(11), share_11);
+		assert_eq!(TemplateModule::pending_rewards(12), share_12);
+		
+		// The validator keeps its third plus the rounding dust, so nothing is lost
+		assert_eq!(TemplateModule::pending_rewards(10), pool - share_11 - share_12);
+		assert!(TemplateModule::pending_rewards(10) > pool / 3);
+	});
+}
+
+#[test]
+fn self_nomination_is_not_rewarded_twice() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		// Inject a legacy self-nomination
+		let nominations = vec![Nomination { validator: 10, amount: 1_000_000_000 }];
+		crate::Nominators::<Test>::insert(10, frame_support::BoundedVec::truncate_from(nominations));
+		
+		assert_eq!(TemplateModule::projected_era_reward(&10), 403_200);
+		run_to_next_era();
+		
+		// Only the validator reward is paid
+		assert_eq!(TemplateModule::pending_rewards(10), 403_200);
+	});
+}
+
+#[test]
+fn validator_earns_only_after_proving_period() {
+	new_test_ext().execute_with(|| {
+		MinProvingEras::set(2);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		// Not yet elected in era 0, first elected for era 1
+		run_to_next_era();
+		assert_eq!(TemplateModule::first_active_era(10), Some(1));
+		
+		// Eras 1 and 2 are the proving period
+		run_to_next_era();
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(10), 0);
+		assert_eq!(TemplateModule::projected_era_reward(&10), 403_200);
+		
+		// Era 3 pays
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(10), 403_200);
+	});
+}
+
+#[test]
+fn sub_existential_reward_is_kept_for_dead_account() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(500);
+		
+		// Account 50 has never held a balance
+		crate::PendingRewards::<Test>::insert(50, 100);
+		assert_noop!(
+			TemplateModule::claim_rewards(RuntimeOrigin::signed(50)),
+			Error::<Test>::RewardBelowExistential
+		);
+		assert_eq!(TemplateModule::pending_rewards(50), 100);
+		
+		// Once enough has accrued the claim goes through
+		crate::PendingRewards::<Test>::insert(50, 500);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(50)));
+		assert_eq!(Balances::free_balance(50), 500);
+		
+		// Existing accounts may claim any amount
+		crate::PendingRewards::<Test>::insert(1, 100);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 1_100);
+	});
+}
+
+#[test]
+fn claim_rewards_fails_when_no_rewards() {
+	new_test_ext().execute_with(|| {
+		// Try to claim rewards when none exist
+		assert_noop!(
+			TemplateModule::claim_rewards(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoRewards
+		);
+	});
+}
+
+#[test]
+fn last_claimed_era_follows_each_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		
+		run_to_next_era();
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(11)));
+		assert_eq!(TemplateModule::last_claimed_era(11), Some(1));
+		
+		run_to_next_era();
+		run_to_next_era();
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(11)));
+		assert_eq!(TemplateModule::last_claimed_era(11), Some(3));
+		assert_eq!(TemplateModule::nominator_dashboard(&11).last_claimed_era, Some(3));
+		
+		// Accrued but never claimed
+		assert_eq!(TemplateModule::last_claimed_era(10), None);
+		assert_eq!(TemplateModule::nominator_dashboard(&10).last_claimed_era, None);
+	});
+}
+
+#[test]
+fn claims_are_limited_to_one_per_interval() {
+	new_test_ext().execute_with(|| {
+		MinClaimInterval::set(20_000);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		
+		run_to_next_era();
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		let first_claim = System::block_number();
+		assert_eq!(TemplateModule::last_claim_block(10), Some(first_claim));
+		
+		// One era later is still within the interval
+		run_to_next_era();
+		assert_noop!(
+			TemplateModule::claim_rewards(RuntimeOrigin::signed(10)),
+			Error::<Test>::ClaimTooSoon
+		);
+		
+		// Once the interval has passed the claim goes through
+		System::set_block_number(first_claim + 20_000);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		assert_eq!(TemplateModule::last_claim_block(10), Some(first_claim + 20_000));
+	});
+}
+
+#[test]
+fn claim_and_restake_is_a_throttled_claim() {
+	new_test_ext().execute_with(|| {
+		MinClaimInterval::set(20_000);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		
+		run_to_next_era();
+		assert_ok!(TemplateModule::claim_and_restake(RuntimeOrigin::signed(10), 11));
+		let first_claim = System::block_number();
+		assert_eq!(TemplateModule::last_claim_block(10), Some(first_claim));
+		assert_eq!(TemplateModule::last_claimed_era(10), Some(1));
+		
+		// Restaking shares the claim interval with plain claims
+		run_to_next_era();
+		assert_noop!(
+			TemplateModule::claim_and_restake(RuntimeOrigin::signed(10), 11),
+			Error::<Test>::ClaimTooSoon
+		);
+		assert_noop!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)), Error::<Test>::ClaimTooSoon);
+		
+		// And makes the same checks on the validator as a nomination
+		System::set_block_number(first_claim + 20_000);
+		assert_ok!(TemplateModule::report_misbehavior(RuntimeOrigin::root(), 11));
+		assert_noop!(
+			TemplateModule::claim_and_restake(RuntimeOrigin::signed(10), 11),
+			Error::<Test>::ValidatorUnderInvestigation
+		);
+		MinStake::set(2_000_000_000);
+		assert_ok!(TemplateModule::resolve_report(RuntimeOrigin::root(), 11));
+		assert_noop!(
+			TemplateModule::claim_and_restake(RuntimeOrigin::signed(10), 11),
+			Error::<Test>::StakeBelowMinimum
+		);
+	});
+}
+
+#[test]
+fn nominator_dashboard_aggregates_nominations_and_rewards() {
+	new_test_ext().execute_with(|| {
+		// Two validators backed by the same nominator
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(11), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(12), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(12), 11, 2_000_000_000));
+		
+		// Each validator's pool (403_200 for its own stake plus the nominator reward of the
+		// nomination) is shared pro rata by stake
+		let reward_10 = Perbill::from_rational(1u32, 2u32).mul_floor(403_200 + 273_600u128);
+		let reward_11 = Perbill::from_rational(2u32, 3u32).mul_floor(403_200 + 547_200u128);
+		
+		run_to_next_era();
+		
+		assert_eq!(
+			TemplateModule::nominator_dashboard(&12),
+			NominatorDashboard {
+				total_bonded: 3_000_000_000,
+				nominations: vec![
+					(10, 1_000_000_000, ValidatorStatus::Active),
+					(11, 2_000_000_000, ValidatorStatus::Active),
+				],
+				pending_rewards: reward_10 + reward_11,
+				estimated_next_era_reward: reward_10 + reward_11,
+				last_claimed_era: None,
+			}
+		);
+		
+		// A departed validator's nomination unbonds and no longer contributes to the projection
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(11)));
+		let dashboard = TemplateModule::nominator_dashboard(&12);
+		assert_eq!(dashboard.total_bonded, 1_000_000_000);
+		assert_eq!(dashboard.nominations, vec![(10, 1_000_000_000, ValidatorStatus::Active)]);
+		assert_eq!(dashboard.estimated_next_era_reward, reward_10);
+	});
+}
+
+#[test]
+fn nominator_dashboard_defaults_for_non_nominator() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			TemplateModule::nominator_dashboard(&3),
+			NominatorDashboard {
+				total_bonded: 0,
+				nominations: vec![],
+				pending_rewards: 0,
+				estimated_next_era_reward: 0,
+				last_claimed_era: None,
+			}
+		);
+	});
+}
+
+#[test]
+fn slash_notifies_nominators_of_validator() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(4), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 1, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 4, 100));
+		
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 10, 0, SlashCategory::Malice));
+		
+		System::assert_has_event(Event::ValidatorSlashed(1, 50, 10, SlashCategory::Malice).into());
+		System::assert_last_event(Event::ValidatorSlashedWithBackers(1, 50, 2).into());
+	});
+}
+
+#[test]
+fn slash_requires_slash_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		// The slashing committee may slash
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::signed(SlashCommittee::get()), 1, 10, 0, SlashCategory::Malice));
+		assert_eq!(TemplateModule::validators(1), 450);
+		assert_ok!(TemplateModule::slash_validators(
+			RuntimeOrigin::signed(SlashCommittee::get()),
+			vec![(1, 10, 0)]
+		));
+		assert_eq!(TemplateModule::validators(1), 405);
+		
+		// Any other signed account may not
+		assert_noop!(
+			TemplateModule::slash_validator(RuntimeOrigin::signed(2), 1, 10, 0, SlashCategory::Malice),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::slash_validators(RuntimeOrigin::signed(2), vec![(1, 10, 0)]),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn slash_is_shared_by_nominators() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(4), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 200));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 1, 100));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(3), 4, 100));
+		
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Malice));
+		
+		// Validator and both of its nominators lose 20%
+		assert_eq!(Balances::reserved_balance(1), 400);
+		assert_eq!(Balances::reserved_balance(2), 160);
+		assert_eq!(Balances::reserved_balance(3), 180);
+		System::assert_has_event(Event::NominatorSlashed(2, 1, 40).into());
+		System::assert_has_event(Event::NominatorSlashed(3, 1, 20).into());
+		
+		// Nominations and totals follow, the nomination of validator 4 is untouched
+		assert_eq!(TemplateModule::total_nominated(&2), 160);
+		assert_eq!(TemplateModule::nominators(3).iter().map(|n| n.amount).collect::<Vec<_>>(), vec![80, 100]);
+		assert_eq!(TemplateModule::total_validator_stake(1), 640);
+		assert_eq!(TemplateModule::total_validator_stake(4), 600);
+		assert_eq!(TemplateModule::total_staked(), 1240);
+	});
+}
+
+#[test]
+fn downtime_slash_spares_nominators() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 200));
+		
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Downtime));
+		System::assert_has_event(Event::ValidatorSlashed(1, 100, 20, SlashCategory::Downtime).into());
+		
+		// Only the validator's own stake is slashed
+		assert_eq!(Balances::reserved_balance(1), 400);
+		assert_eq!(Balances::reserved_balance(2), 200);
+		assert_eq!(TemplateModule::total_nominated(&2), 200);
+		assert_eq!(TemplateModule::total_validator_stake(1), 600);
+		assert_eq!(Balances::free_balance(SLASH_DESTINATION), 100);
+		
+		// A malice slash also takes from the nominator
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Malice));
+		System::assert_has_event(Event::ValidatorSlashed(1, 80, 20, SlashCategory::Malice).into());
+		assert_eq!(Balances::reserved_balance(1), 320);
+		assert_eq!(Balances::reserved_balance(2), 160);
+	});
+}
+
+#[test]
+fn validator_in_grace_period_is_slashable_from_a_future_era() {
+	new_test_ext().execute_with(|| {
+		SlashGracePeriod::set(2);
+		run_to_next_era();
+		
+		assert_eq!(TemplateModule::slashable_from_era(&1), None);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_eq!(TemplateModule::registered_at_era(1), 1);
+		assert_eq!(TemplateModule::slashable_from_era(&1), Some(3));
+		
+		// Offences committed during the grace period are not slashed
+		assert_noop!(
+			TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 1, SlashCategory::Malice),
+			Error::<Test>::InSlashGracePeriod
+		);
+	});
+}
+
+#[test]
+fn validator_past_grace_period_is_slashable() {
+	new_test_ext().execute_with(|| {
+		SlashGracePeriod::set(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000));
+		run_to_next_era();
+		run_to_next_era();
+		
+		// Slashable since era 1, before the current era 2
+		assert_eq!(TemplateModule::slashable_from_era(&10), Some(1));
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 10, 20, 1, SlashCategory::Malice));
+		
+		// Removal forgets the registration era
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(10)));
+		assert_eq!(TemplateModule::slashable_from_era(&10), None);
+	});
+}
+
+#[test]
+fn removed_validator_is_not_slashed_for_its_grace_period() {
+	new_test_ext().execute_with(|| {
+		SlashGracePeriod::set(2);
+		run_to_next_era();
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		run_to_next_era();
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		
+		// The offence era is still inside the grace period that started at registration
+		assert_noop!(
+			TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 2, SlashCategory::Malice),
+			Error::<Test>::InSlashGracePeriod
+		);
+		assert_eq!(TemplateModule::registered_at_era(1), 1);
+		
+		// The registration era goes with the last snapshot
+		for _ in 0..=UnbondingPeriod::get() {
+			run_to_next_era();
+		}
+		assert!(!crate::RegisteredAtEra::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn slashed_funds_go_to_the_slash_handler() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(2), 1, 200));
+		let issuance = Balances::total_issuance();
+		
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Malice));
+		
+		// 100 from the validator and 40 from its nominator reach the destination, none is burned
+		assert_eq!(Balances::free_balance(SLASH_DESTINATION), 140);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+#[test]
+fn offence_history_is_bounded_and_cleared_with_the_validator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 10, 0, SlashCategory::Malice));
+		run_to_next_era();
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 10, 1, SlashCategory::Malice));
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Malice));
+		
+		// Only the latest MaxOffenceHistory (2) offences are kept, all are counted
+		assert_eq!(TemplateModule::offence_history(1).into_inner(), vec![(1, 10), (0, 20)]);
+		assert_eq!(TemplateModule::offence_count(1), 3);
+		
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		assert!(!crate::OffenceHistory::<Test>::contains_key(1));
+		assert!(!crate::OffenceCount::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn slash_applies_to_stake_at_offence_era() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		run_to_next_era();
+		assert_eq!(TemplateModule::era_stake_snapshot(1, 1), Some(500));
+		
+		// After an offence in era 1 the validator unbonds most of its stake
+		assert_ok!(TemplateModule::unbond(RuntimeOrigin::signed(1), 400));
+		run_to_next_era();
+		
+		// 20% of the 500 bonded in era 1, taken from the stake still unbonding
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 1, SlashCategory::Malice));
+		System::assert_has_event(Event::ValidatorSlashed(1, 100, 20, SlashCategory::Malice).into());
+		assert_eq!(TemplateModule::validators(1), 100);
+		assert_eq!(TemplateModule::unbonding(1).into_inner(), vec![(300, 3)]);
+		assert_eq!(Balances::reserved_balance(1), 400);
+		
+		// Offences must lie within the unbonding period and not in the future
+		assert_noop!(
+			TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 3, SlashCategory::Malice),
+			Error::<Test>::InvalidOffenceEra
+		);
+		run_to_next_era();
+		run_to_next_era();
+		assert_noop!(
+			TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 1, SlashCategory::Malice),
+			Error::<Test>::InvalidOffenceEra
+		);
+		assert_eq!(TemplateModule::era_stake_snapshot(1, 1), None);
+	});
+}
+
+#[test]
+fn removing_validator_after_offence_does_not_dodge_slash() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		run_to_next_era();
+		
+		// After an offence in era 1 the validator leaves, its stake starts unbonding
+		assert_ok!(TemplateModule::remove_validator(RuntimeOrigin::signed(1)));
+		assert_eq!(TemplateModule::validators(1), 0);
+		
+		// 20% of the 500 bonded in era 1 is taken from the unbonding stake
+		assert_ok!(TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 1, SlashCategory::Malice));
+		System::assert_has_event(Event::ValidatorSlashed(1, 100, 20, SlashCategory::Malice).into());
+		assert_eq!(TemplateModule::unbonding(1).into_inner(), vec![(400, 3)]);
+		assert_eq!(Balances::reserved_balance(1), 400);
+		assert_eq!(Balances::free_balance(SLASH_DESTINATION), 100);
+		assert!(!TemplateModule::chilled(1));
+		
+		// Without a snapshot for the offence era there is nothing to slash against
+		assert_noop!(
+			TemplateModule::slash_validator(RuntimeOrigin::root(), 1, 20, 0, SlashCategory::Malice),
+			Error::<Test>::NotValidator
+		);
+	});
+}
+
+#[test]
+fn slash_validators_skips_non_validators() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(2), 400));
+		
+		// Account 3 is not a validator and is skipped
+		assert_ok!(TemplateModule::slash_validators(
+			RuntimeOrigin::root(),
+			vec![(1, 10, 0), (3, 50, 0), (2, 50, 0)]
+		));
+		
+		assert_eq!(TemplateModule::validators(1), 450);
+		assert_eq!(TemplateModule::validators(2), 200);
+		assert_eq!(TemplateModule::total_staked(), 650);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		
+		System::assert_has_event(Event::ValidatorSlashed(1, 50, 10, SlashCategory::Malice).into());
+		System::assert_has_event(Event::ValidatorSlashed(2, 200, 50, SlashCategory::Malice).into());
+		System::assert_last_event(Event::ValidatorsSlashed(2, 250).into());
+	});
+}
+
+#[test]
+fn slash_validators_rejects_invalid_batches() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		assert_noop!(
+			TemplateModule::slash_validators(RuntimeOrigin::signed(1), vec![(1, 10, 0)]),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::slash_validators(RuntimeOrigin::root(), vec![(1, 10, 0), (1, 101, 0)]),
+			Error::<Test>::InvalidSlashPercentage
+		);
+		assert_noop!(
+			TemplateModule::slash_validators(RuntimeOrigin::root(), vec![(1, 1, 0); 5]),
+			Error::<Test>::TooManySlashes
+		);
+	});
+}
+
+#[test]
+fn claim_and_restake_tops_up_nomination() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		
+		run_to_next_era();
+		
+		let rewards = TemplateModule::pending_rewards(11);
+		assert!(rewards > 0);
+		let free_before = Balances::free_balance(11);
+		let reserved_before = Balances::reserved_balance(11);
+		
+		assert_ok!(TemplateModule::claim_and_restake(RuntimeOrigin::signed(11), 10));
+		
+		// The reward went straight into the nomination, free balance is untouched
+		assert_eq!(TemplateModule::pending_rewards(11), 0);
+		assert_eq!(TemplateModule::nominators(11)[0].amount, 1_000_000_000 + rewards);
+		assert_eq!(Balances::free_balance(11), free_before);
+		assert_eq!(Balances::reserved_balance(11), reserved_before + rewards);
+		assert_eq!(TemplateModule::total_validator_stake(10), 2_000_000_000 + rewards);
+		
+		System::assert_has_event(Event::RewardsClaimed(11, rewards).into());
+		System::assert_last_event(Event::Nomination(11, 10, rewards).into());
+	});
+}
+
+#[test]
+fn rewards_are_paid_to_the_payee_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::set_payee(RuntimeOrigin::signed(10), RewardDestination::Account(20)));
+		System::assert_last_event(Event::PayeeSet(10, RewardDestination::Account(20)).into());
+		
+		run_to_next_era();
+		let free_before = Balances::free_balance(10);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		
+		assert_eq!(Balances::free_balance(20), 403_200);
+		assert_eq!(Balances::free_balance(10), free_before);
+		
+		// Back to the default destination
+		assert_ok!(TemplateModule::set_payee(RuntimeOrigin::signed(10), RewardDestination::Stash));
+		assert!(!crate::Payee::<Test>::contains_key(10));
+		run_to_next_era();
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		assert_eq!(Balances::free_balance(10), free_before + TemplateModule::prev_era_pool());
+	});
+}
+
+#[test]
+fn staked_rewards_compound_into_validator_stake() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::set_payee(RuntimeOrigin::signed(10), RewardDestination::Staked));
+		
+		run_to_next_era();
+		let free_before = Balances::free_balance(10);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(10)));
+		
+		assert_eq!(TemplateModule::validators(10), 1_000_403_200);
+		assert_eq!(TemplateModule::total_validator_stake(10), 1_000_403_200);
+		assert_eq!(TemplateModule::total_staked(), 1_000_403_200);
+		assert_eq!(Balances::reserved_balance(10), 1_000_403_200);
+		assert_eq!(Balances::free_balance(10), free_before);
+		System::assert_has_event(Event::ValidatorBondedExtra(10, 403_200).into());
+		System::assert_has_event(Event::ValidatorStakeIncreased(10, 1_000_403_200).into());
+	});
+}
+
+#[test]
+fn staked_rewards_compound_into_nomination() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		assert_ok!(TemplateModule::nominate(RuntimeOrigin::signed(11), 10, 1_000_000_000));
+		assert_ok!(TemplateModule::set_payee(RuntimeOrigin::signed(11), RewardDestination::Staked));
+		
+		run_to_next_era();
+		let rewards = TemplateModule::pending_rewards(11);
+		assert!(rewards > 0);
+		let free_before = Balances::free_balance(11);
+		assert_ok!(TemplateModule::claim_rewards(RuntimeOrigin::signed(11)));
+		
+		assert_eq!(TemplateModule::nominators(11)[0].amount, 1_000_000_000 + rewards);
+		assert_eq!(TemplateModule::total_validator_stake(10), 2_000_000_000 + rewards);
+		assert_eq!(Balances::reserved_balance(11), 1_000_000_000 + rewards);
+		assert_eq!(Balances::free_balance(11), free_before);
+		System::assert_has_event(Event::Nomination(11, 10, rewards).into());
+	});
+}
+
+#[test]
+fn claim_and_restake_fails_without_rewards_or_validator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(1), 500));
+		
+		assert_noop!(
+			TemplateModule::claim_and_restake(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::NoRewards
+		);
+		assert_noop!(
+			TemplateModule::claim_and_restake(RuntimeOrigin::signed(2), 3),
+			Error::<Test>::NotValidator
+		);
+	});
+}
+
+#[test]
+fn pending_rewards_above_cap_are_paid_out() {
+	new_test_ext().execute_with(|| {
+		// 403_200 per era against a MaxPendingReward of 1_000_000
+		assert_ok!(TemplateModule::register_validator(RuntimeOrigin::signed(10), 1_000_000_000));
+		let era_reward = 1_000_000_000 * 15 / 525_600_000 * 14_400;
+		let free_before = Balances::free_balance(10);
+		
+		run_to_next_era();
+		run_to_next_era();
+		assert_eq!(TemplateModule::pending_rewards(10), 2 * era_reward);
+		assert_eq!(Balances::free_balance(10), free_before);
+		
+		// The third era would cross the cap, so the two accrued eras are paid out first
+		run_to_next_era();
+		System::assert_has_event(Event::RewardsClaimed(10, 2 * era_reward).into());
+		assert_eq!(Balances::free_balance(10), free_before + 2 * era_reward);
+		assert_eq!(TemplateModule::pending_rewards(10), era_reward);
+	});
+}
@@ -1,29 +1,9 @@
 
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_template
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ../../target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --pallet
-// pallet_template
-// --extrinsic
-// *
-// --steps=50
-// --repeat=20
-// --wasm-execution=compiled
-// --output
-// pallets/template/src/weights.rs
-// --template
-// ../../.maintain/frame-weight-template.hbs
+//! The staking weights are estimated from each call's storage access and have not been
+//! measured yet. Regenerate this file with `benchmark pallet --pallet pallet_template
+//! --extrinsic '*'` on reference hardware before relying on them.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -36,6 +16,31 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn do_something() -> Weight;
 	fn cause_error() -> Weight;
+	fn register_validator() -> Weight;
+	fn remove_validator(n: u32) -> Weight;
+	fn nominate() -> Weight;
+	fn withdraw_nomination() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn slash_validator(n: u32) -> Weight;
+	fn force_unbond_all(n: u32) -> Weight;
+	fn new_era(v: u32, n: u32) -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn bond_extra() -> Weight;
+	fn unbond() -> Weight;
+	fn set_commission() -> Weight;
+	fn chill() -> Weight;
+	fn unchill() -> Weight;
+	fn set_validator_metadata() -> Weight;
+	fn set_payee() -> Weight;
+	fn add_to_whitelist() -> Weight;
+	fn remove_from_whitelist() -> Weight;
+	fn set_nomination_limit() -> Weight;
+	fn set_minting_paused() -> Weight;
+	fn report_misbehavior() -> Weight;
+	fn increase_nomination() -> Weight;
+	fn decrease_nomination() -> Weight;
+	fn claim_commission() -> Weight;
+	fn resolve_report(n: u32) -> Weight;
 }
 
 /// Weights for pallet_template using the Substrate node and recommended hardware.
@@ -62,6 +67,321 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template ValidatorWhitelist (r:1 w:0)
+	/// Storage: Template ValidatorCount (r:1 w:1)
+	/// Storage: Template NewValidatorsThisEra (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template BondedSinceEra (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:0 w:1)
+	/// Storage: Template RegisteredAtEra (r:0 w:1)
+	fn register_validator() -> Weight {
+		Weight::from_parts(45_000_000, 3_541)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template EraStakeSnapshot (r:1 w:1)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: Template ActiveSet (r:1 w:1)
+	/// Storage: Template Nominators (r:513 w:512)
+	/// Storage: Template NominatorsByValidator (r:1 w:1)
+	/// Storage: Template NominatorCount (r:1 w:1)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: System Account (r:512 w:0)
+	/// Storage: Template ValidatorCount (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:0 w:1)
+	/// Storage: Template ValidatorCommission (r:0 w:1)
+	/// Storage: Template Chilled (r:0 w:1)
+	/// Storage: Template ZeroStakeEras (r:0 w:1)
+	/// Storage: Template FirstActiveEra (r:0 w:1)
+	/// Storage: Template ValidatorMetadata (r:0 w:1)
+	/// Storage: Template OffenceHistory (r:0 w:1)
+	/// Storage: Template OffenceCount (r:0 w:1)
+	/// Storage: Template BondedSinceEra (r:0 w:513)
+	/// The range of component `n` is `[0, 512]`.
+	fn remove_validator(n: u32) -> Weight {
+		Weight::from_parts(52_000_000, 4_764)
+			.saturating_add(Weight::from_parts(27_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template ActiveSet (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: Template NominationLimit (r:1 w:0)
+	/// Storage: Template NominatorsByValidator (r:1 w:1)
+	/// Storage: Template NominatorCount (r:1 w:1)
+	/// Storage: Template BondedSinceEra (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	fn nominate() -> Weight {
+		Weight::from_parts(58_000_000, 18_163)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: Template NominatorsByValidator (r:1 w:1)
+	/// Storage: Template NominatorCount (r:1 w:1)
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template BondedSinceEra (r:0 w:1)
+	fn withdraw_nomination() -> Weight {
+		Weight::from_parts(55_000_000, 18_163)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Template MintingPaused (r:1 w:0)
+	/// Storage: Template PendingRewards (r:1 w:1)
+	/// Storage: Template LastClaimBlock (r:1 w:1)
+	/// Storage: Template Payee (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Nominators (r:1 w:0)
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template LastClaimedEra (r:0 w:1)
+	fn claim_rewards() -> Weight {
+		Weight::from_parts(60_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template RegisteredAtEra (r:1 w:0)
+	/// Storage: Template EraStakeSnapshot (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:0)
+	/// Storage: Template Chilled (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: System Account (r:514 w:514)
+	/// Storage: Template NominatorsByValidator (r:1 w:0)
+	/// Storage: Template Nominators (r:512 w:512)
+	/// Storage: Template OffenceHistory (r:1 w:1)
+	/// Storage: Template OffenceCount (r:1 w:1)
+	/// The range of component `n` is `[0, 512]`.
+	fn slash_validator(n: u32) -> Weight {
+		Weight::from_parts(62_000_000, 6_196)
+			.saturating_add(Weight::from_parts(31_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template ForceUnbondCursor (r:1 w:1)
+	/// Storage: Template Unbonding (r:1000 w:1000)
+	/// Storage: System Account (r:1000 w:1000)
+	/// The range of component `n` is `[1, 1000]`.
+	fn force_unbond_all(n: u32) -> Weight {
+		Weight::from_parts(8_000_000, 1_489)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template ValidatorCount (r:1 w:0)
+	/// Storage: Template NominatorCount (r:1 w:0)
+	/// Storage: Template CurrentEra (r:1 w:1)
+	/// Storage: Template EraStartBlock (r:0 w:1)
+	/// Storage: Template NewValidatorsThisEra (r:0 w:1)
+	/// Storage: Template TotalStaked (r:1 w:0)
+	/// Storage: Template PrevEraPool (r:1 w:1)
+	/// Storage: Template MintingPaused (r:1 w:0)
+	/// Storage: Template PendingTreasuryCut (r:1 w:1)
+	/// Storage: Template EraTotalRewards (r:0 w:2)
+	/// Storage: Template ActiveSet (r:1 w:1)
+	/// Storage: Template Validators (r:1001 w:0)
+	/// Storage: Template TotalValidatorStake (r:1000 w:0)
+	/// Storage: Template ValidatorCommission (r:1000 w:0)
+	/// Storage: Template RegisteredAtEra (r:1000 w:1000)
+	/// Storage: Template Chilled (r:1000 w:0)
+	/// Storage: Template ZeroStakeEras (r:1000 w:1000)
+	/// Storage: Template CommissionEarned (r:1000 w:1000)
+	/// Storage: Template EraStakeSnapshot (r:2000 w:2000)
+	/// Storage: Template NominatorsByValidator (r:1000 w:0)
+	/// Storage: Template Nominators (r:512 w:0)
+	/// Storage: Template BondedSinceEra (r:513 w:0)
+	/// Storage: Template Payee (r:513 w:0)
+	/// Storage: Template PendingRewards (r:513 w:513)
+	/// Storage: Template EraRewards (r:0 w:513)
+	/// The range of component `v` is `[1, 1000]`.
+	/// The range of component `n` is `[0, 512]`.
+	fn new_era(v: u32, n: u32) -> Weight {
+		Weight::from_parts(30_000_000, 4_012)
+			.saturating_add(Weight::from_parts(38_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().reads((13_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(34_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	fn bond_extra() -> Weight {
+		Weight::from_parts(38_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template EraStakeSnapshot (r:1 w:1)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template BondedSinceEra (r:0 w:1)
+	fn unbond() -> Weight {
+		Weight::from_parts(41_000_000, 3_541)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template ValidatorCommission (r:0 w:1)
+	fn set_commission() -> Weight {
+		Weight::from_parts(16_000_000, 3_541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template Chilled (r:1 w:1)
+	fn chill() -> Weight {
+		Weight::from_parts(18_000_000, 3_541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Chilled (r:1 w:1)
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template TotalValidatorStake (r:1 w:0)
+	fn unchill() -> Weight {
+		Weight::from_parts(21_000_000, 3_541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template ValidatorMetadata (r:0 w:1)
+	fn set_validator_metadata() -> Weight {
+		Weight::from_parts(20_000_000, 3_541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Payee (r:0 w:1)
+	fn set_payee() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template ValidatorWhitelist (r:0 w:1)
+	fn add_to_whitelist() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template ValidatorWhitelist (r:0 w:1)
+	fn remove_from_whitelist() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template NominationLimit (r:0 w:1)
+	fn set_nomination_limit() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template MintingPaused (r:0 w:1)
+	/// Storage: Template PendingTreasuryCut (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	fn set_minting_paused() -> Weight {
+		Weight::from_parts(32_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template UnderInvestigation (r:1 w:1)
+	fn report_misbehavior() -> Weight {
+		Weight::from_parts(17_000_000, 3_541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: Template ActiveSet (r:1 w:0)
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	fn increase_nomination() -> Weight {
+		Weight::from_parts(47_000_000, 18_163)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template BondedSinceEra (r:0 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:1)
+	fn decrease_nomination() -> Weight {
+		Weight::from_parts(44_000_000, 18_163)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Template MintingPaused (r:1 w:0)
+	/// Storage: Template CommissionEarned (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	fn claim_commission() -> Weight {
+		Weight::from_parts(36_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Template UnderInvestigation (r:1 w:1)
+	/// Storage: Template HeldWithdrawals (r:513 w:512)
+	/// Storage: System Account (r:512 w:512)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Unbonding (r:512 w:512)
+	/// The range of component `n` is `[0, 512]`.
+	fn resolve_report(n: u32) -> Weight {
+		Weight::from_parts(20_000_000, 3_541)
+			.saturating_add(Weight::from_parts(26_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -87,4 +407,319 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template ValidatorWhitelist (r:1 w:0)
+	/// Storage: Template ValidatorCount (r:1 w:1)
+	/// Storage: Template NewValidatorsThisEra (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template BondedSinceEra (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:0 w:1)
+	/// Storage: Template RegisteredAtEra (r:0 w:1)
+	fn register_validator() -> Weight {
+		Weight::from_parts(45_000_000, 3_541)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template EraStakeSnapshot (r:1 w:1)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: Template ActiveSet (r:1 w:1)
+	/// Storage: Template Nominators (r:513 w:512)
+	/// Storage: Template NominatorsByValidator (r:1 w:1)
+	/// Storage: Template NominatorCount (r:1 w:1)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: System Account (r:512 w:0)
+	/// Storage: Template ValidatorCount (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:0 w:1)
+	/// Storage: Template ValidatorCommission (r:0 w:1)
+	/// Storage: Template Chilled (r:0 w:1)
+	/// Storage: Template ZeroStakeEras (r:0 w:1)
+	/// Storage: Template FirstActiveEra (r:0 w:1)
+	/// Storage: Template ValidatorMetadata (r:0 w:1)
+	/// Storage: Template OffenceHistory (r:0 w:1)
+	/// Storage: Template OffenceCount (r:0 w:1)
+	/// Storage: Template BondedSinceEra (r:0 w:513)
+	/// The range of component `n` is `[0, 512]`.
+	fn remove_validator(n: u32) -> Weight {
+		Weight::from_parts(52_000_000, 4_764)
+			.saturating_add(Weight::from_parts(27_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template ActiveSet (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: Template NominationLimit (r:1 w:0)
+	/// Storage: Template NominatorsByValidator (r:1 w:1)
+	/// Storage: Template NominatorCount (r:1 w:1)
+	/// Storage: Template BondedSinceEra (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	fn nominate() -> Weight {
+		Weight::from_parts(58_000_000, 18_163)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: Template NominatorsByValidator (r:1 w:1)
+	/// Storage: Template NominatorCount (r:1 w:1)
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template BondedSinceEra (r:0 w:1)
+	fn withdraw_nomination() -> Weight {
+		Weight::from_parts(55_000_000, 18_163)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Template MintingPaused (r:1 w:0)
+	/// Storage: Template PendingRewards (r:1 w:1)
+	/// Storage: Template LastClaimBlock (r:1 w:1)
+	/// Storage: Template Payee (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Nominators (r:1 w:0)
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template LastClaimedEra (r:0 w:1)
+	fn claim_rewards() -> Weight {
+		Weight::from_parts(60_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template RegisteredAtEra (r:1 w:0)
+	/// Storage: Template EraStakeSnapshot (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:0)
+	/// Storage: Template Chilled (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: System Account (r:514 w:514)
+	/// Storage: Template NominatorsByValidator (r:1 w:0)
+	/// Storage: Template Nominators (r:512 w:512)
+	/// Storage: Template OffenceHistory (r:1 w:1)
+	/// Storage: Template OffenceCount (r:1 w:1)
+	/// The range of component `n` is `[0, 512]`.
+	fn slash_validator(n: u32) -> Weight {
+		Weight::from_parts(62_000_000, 6_196)
+			.saturating_add(Weight::from_parts(31_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template ForceUnbondCursor (r:1 w:1)
+	/// Storage: Template Unbonding (r:1000 w:1000)
+	/// Storage: System Account (r:1000 w:1000)
+	/// The range of component `n` is `[1, 1000]`.
+	fn force_unbond_all(n: u32) -> Weight {
+		Weight::from_parts(8_000_000, 1_489)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template ValidatorCount (r:1 w:0)
+	/// Storage: Template NominatorCount (r:1 w:0)
+	/// Storage: Template CurrentEra (r:1 w:1)
+	/// Storage: Template EraStartBlock (r:0 w:1)
+	/// Storage: Template NewValidatorsThisEra (r:0 w:1)
+	/// Storage: Template TotalStaked (r:1 w:0)
+	/// Storage: Template PrevEraPool (r:1 w:1)
+	/// Storage: Template MintingPaused (r:1 w:0)
+	/// Storage: Template PendingTreasuryCut (r:1 w:1)
+	/// Storage: Template EraTotalRewards (r:0 w:2)
+	/// Storage: Template ActiveSet (r:1 w:1)
+	/// Storage: Template Validators (r:1001 w:0)
+	/// Storage: Template TotalValidatorStake (r:1000 w:0)
+	/// Storage: Template ValidatorCommission (r:1000 w:0)
+	/// Storage: Template RegisteredAtEra (r:1000 w:1000)
+	/// Storage: Template Chilled (r:1000 w:0)
+	/// Storage: Template ZeroStakeEras (r:1000 w:1000)
+	/// Storage: Template CommissionEarned (r:1000 w:1000)
+	/// Storage: Template EraStakeSnapshot (r:2000 w:2000)
+	/// Storage: Template NominatorsByValidator (r:1000 w:0)
+	/// Storage: Template Nominators (r:512 w:0)
+	/// Storage: Template BondedSinceEra (r:513 w:0)
+	/// Storage: Template Payee (r:513 w:0)
+	/// Storage: Template PendingRewards (r:513 w:513)
+	/// Storage: Template EraRewards (r:0 w:513)
+	/// The range of component `v` is `[1, 1000]`.
+	/// The range of component `n` is `[0, 512]`.
+	fn new_era(v: u32, n: u32) -> Weight {
+		Weight::from_parts(30_000_000, 4_012)
+			.saturating_add(Weight::from_parts(38_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().reads((13_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(34_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	fn bond_extra() -> Weight {
+		Weight::from_parts(38_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Template Validators (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template EraStakeSnapshot (r:1 w:1)
+	/// Storage: Template Unbonding (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template BondedSinceEra (r:0 w:1)
+	fn unbond() -> Weight {
+		Weight::from_parts(41_000_000, 3_541)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template ValidatorCommission (r:0 w:1)
+	fn set_commission() -> Weight {
+		Weight::from_parts(16_000_000, 3_541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template Chilled (r:1 w:1)
+	fn chill() -> Weight {
+		Weight::from_parts(18_000_000, 3_541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Chilled (r:1 w:1)
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template TotalValidatorStake (r:1 w:0)
+	fn unchill() -> Weight {
+		Weight::from_parts(21_000_000, 3_541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template ValidatorMetadata (r:0 w:1)
+	fn set_validator_metadata() -> Weight {
+		Weight::from_parts(20_000_000, 3_541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Payee (r:0 w:1)
+	fn set_payee() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template ValidatorWhitelist (r:0 w:1)
+	fn add_to_whitelist() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template ValidatorWhitelist (r:0 w:1)
+	fn remove_from_whitelist() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template NominationLimit (r:0 w:1)
+	fn set_nomination_limit() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template MintingPaused (r:0 w:1)
+	/// Storage: Template PendingTreasuryCut (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	fn set_minting_paused() -> Weight {
+		Weight::from_parts(32_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template UnderInvestigation (r:1 w:1)
+	fn report_misbehavior() -> Weight {
+		Weight::from_parts(17_000_000, 3_541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: Template ActiveSet (r:1 w:0)
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	fn increase_nomination() -> Weight {
+		Weight::from_parts(47_000_000, 18_163)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Template Nominators (r:1 w:1)
+	/// Storage: Template Validators (r:1 w:0)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template BondedSinceEra (r:0 w:1)
+	/// Storage: Template TotalValidatorStake (r:1 w:1)
+	/// Storage: Template TotalStaked (r:1 w:1)
+	/// Storage: Template UnderInvestigation (r:1 w:0)
+	/// Storage: Template Unbonding (r:1 w:1)
+	fn decrease_nomination() -> Weight {
+		Weight::from_parts(44_000_000, 18_163)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Template MintingPaused (r:1 w:0)
+	/// Storage: Template CommissionEarned (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	fn claim_commission() -> Weight {
+		Weight::from_parts(36_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Template UnderInvestigation (r:1 w:1)
+	/// Storage: Template HeldWithdrawals (r:513 w:512)
+	/// Storage: System Account (r:512 w:512)
+	/// Storage: Template CurrentEra (r:1 w:0)
+	/// Storage: Template Unbonding (r:512 w:512)
+	/// The range of component `n` is `[0, 512]`.
+	fn resolve_report(n: u32) -> Weight {
+		Weight::from_parts(20_000_000, 3_541)
+			.saturating_add(Weight::from_parts(26_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
 }
@@ -42,8 +42,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Block, Executive, Grandpa, InherentDataExt, Nonce, Runtime,
-	RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment, VERSION,
+	AccountId, Aura, Balance, Block, BlockNumber, Executive, Grandpa, Hash, InherentDataExt,
+	Messaging, Nonce, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, Template,
+	TransactionPayment, VERSION,
 };
 
 impl_runtime_apis! {
@@ -220,6 +221,86 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl secura_staking_runtime_api::SecuraStakingApi<Block, AccountId, Balance> for Runtime {
+		fn pending_rewards(account: AccountId) -> Balance {
+			Template::pending_rewards(account)
+		}
+
+		fn projected_era_reward(account: AccountId) -> Balance {
+			Template::projected_era_reward(&account)
+		}
+
+		fn nominator_dashboard(
+			account: AccountId,
+		) -> secura_staking_runtime_api::NominatorDashboard<AccountId, Balance> {
+			Template::nominator_dashboard(&account)
+		}
+
+		fn validator_effective_apr(validator: AccountId) -> sp_runtime::Perbill {
+			Template::validator_effective_apr(&validator)
+		}
+
+		fn self_stake_ratio(validator: AccountId) -> sp_runtime::Perbill {
+			Template::self_stake_ratio(&validator)
+		}
+
+		fn simulate_era_rewards() -> Vec<(AccountId, Balance)> {
+			Template::simulate_era_rewards()
+		}
+
+		fn has_claimable(account: AccountId) -> bool {
+			Template::has_claimable(&account) || Messaging::unread_count(&account) > 0
+		}
+
+		fn slashable_from_era(validator: AccountId) -> Option<secura_staking_runtime_api::EraIndex> {
+			Template::slashable_from_era(&validator)
+		}
+	}
+
+	impl secura_messaging_runtime_api::SecuraMessagingApi<
+		Block,
+		AccountId,
+		Hash,
+		BlockNumber,
+		pallet_messaging::MessageOf<Runtime>,
+	> for Runtime {
+		fn group_thread(group_id: Hash, root_id: Hash) -> Vec<(Hash, Option<Hash>)> {
+			Messaging::group_thread(group_id, root_id)
+		}
+
+		fn group_messages_by_sender(group_id: Hash, sender: AccountId) -> Vec<Hash> {
+			Messaging::group_messages_by_sender(group_id, sender)
+		}
+
+		fn message_group(message_id: Hash) -> Option<Hash> {
+			Messaging::message_group(message_id)
+		}
+
+		fn forward_chain(message_id: Hash) -> Vec<Hash> {
+			Messaging::forward_chain(message_id)
+		}
+
+		fn messages_in_range(account: AccountId, from: BlockNumber, to: BlockNumber) -> Vec<Hash> {
+			Messaging::messages_in_range(account, from, to)
+		}
+
+		fn inbox_page(
+			account: AccountId,
+			start: u32,
+			count: u32,
+		) -> Vec<(Hash, pallet_messaging::MessageOf<Runtime>)> {
+			Messaging::inbox_page(account, start, count)
+		}
+
+		fn outbox_page(
+			account: AccountId,
+			start: u32,
+			count: u32,
+		) -> Vec<(Hash, pallet_messaging::MessageOf<Runtime>)> {
+			Messaging::outbox_page(account, start, count)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -26,16 +26,22 @@
 // Substrate and Polkadot dependencies
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, VariantCountOf},
+	traits::{
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Currency, OnUnbalanced, VariantCountOf,
+	},
 	weights::{
 		constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND},
 		IdentityFee, Weight,
 	},
+	PalletId,
 };
 use frame_system::limits::{BlockLength, BlockWeights};
 use pallet_transaction_payment::{ConstFeeMultiplier, FungibleAdapter, Multiplier};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_runtime::{traits::One, Perbill};
+use sp_runtime::{
+	traits::{AccountIdConversion, One},
+	Perbill,
+};
 use sp_version::RuntimeVersion;
 
 // Local module imports
@@ -43,7 +49,14 @@ use super::{
 	AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Nonce, PalletInfo, Runtime,
 	RuntimeCall, RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask,
 	System, EXISTENTIAL_DEPOSIT, SLOT_DURATION, VERSION, MIN_VALIDATOR_STAKE, MIN_NOMINATION,
-	MAX_NOMINATIONS_PER_NOMINATOR, REWARD_RATE_PER_BLOCK,
+	MAX_NOMINATIONS_PER_NOMINATOR, REWARD_RATE_PER_BLOCK, MAX_PENDING_REWARD, MAX_BATCH_SLASHES,
+	MAX_REWARD_GROWTH_PERCENT, REWARD_START_ERA, MAX_ACTIVE_VALIDATORS, UNBONDING_PERIOD_ERAS,
+	MAX_UNBONDING_CHUNKS, MIN_PROVING_ERAS, MAX_NOMINATORS_PER_VALIDATOR, MIN_CLAIM_INTERVAL,
+	MAX_VALIDATORS, HISTORY_DEPTH_ERAS, MAX_ZERO_STAKE_ERAS, TREASURY_CUT_PERCENT,
+	ERA_DURATION, VALIDATOR_INFLATION_PERCENT, NOMINATOR_INFLATION_PERCENT, MIN_NOMINATORS,
+	MAX_LOYALTY_BONUS_PERCENT, LOYALTY_RAMP_ERAS, MAX_OFFENCE_HISTORY, MAX_NEW_VALIDATORS_PER_ERA,
+	SOFT_COMMISSION_CAP_PERCENT, MESSAGE_DEPOSIT, GROUP_DEPOSIT, SLASH_GRACE_PERIOD_ERAS,
+	MAX_TOTAL_MESSAGES, MIN_SELF_STAKE_RATIO_PERCENT,
 };
 
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
@@ -158,6 +171,25 @@ impl pallet_sudo::Config for Runtime {
 	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const TreasuryCut: Perbill = Perbill::from_percent(TREASURY_CUT_PERCENT);
+	pub TreasuryAccount: AccountId = PalletId(*b"py/trsry").into_account_truncating();
+	pub DustHandling: pallet_template::DustPolicy<AccountId> = pallet_template::DustPolicy::Collect(TreasuryAccount::get());
+	pub const ValidatorInflation: Perbill = Perbill::from_percent(VALIDATOR_INFLATION_PERCENT);
+	pub const NominatorInflation: Perbill = Perbill::from_percent(NOMINATOR_INFLATION_PERCENT);
+	pub const MaxLoyaltyBonus: Perbill = Perbill::from_percent(MAX_LOYALTY_BONUS_PERCENT);
+	pub const SoftCommissionCap: Perbill = Perbill::from_percent(SOFT_COMMISSION_CAP_PERCENT);
+	pub const MinSelfStakeRatio: Perbill = Perbill::from_percent(MIN_SELF_STAKE_RATIO_PERCENT);
+}
+
+/// Slashed stake goes to the treasury account instead of being burned.
+pub struct SlashToTreasury;
+impl OnUnbalanced<pallet_template::NegativeImbalanceOf<Runtime>> for SlashToTreasury {
+	fn on_nonzero_unbalanced(amount: pallet_template::NegativeImbalanceOf<Runtime>) {
+		Balances::resolve_creating(&TreasuryAccount::get(), amount);
+	}
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -166,13 +198,62 @@ impl pallet_template::Config for Runtime {
 	type MinNomination = ConstU128<MIN_NOMINATION>;
 	type MaxNominations = ConstU32<MAX_NOMINATIONS_PER_NOMINATOR>;
 	type RewardRate = ConstU32<REWARD_RATE_PER_BLOCK>;
+	type MaxPendingReward = ConstU128<MAX_PENDING_REWARD>;
+	type MaxBatchSlashes = ConstU32<MAX_BATCH_SLASHES>;
+	type MaxRewardGrowth = ConstU32<MAX_REWARD_GROWTH_PERCENT>;
+	type RewardStartEra = ConstU32<REWARD_START_ERA>;
+	type MaxActiveValidators = ConstU32<MAX_ACTIVE_VALIDATORS>;
+	type RewardActiveSetOnly = ConstBool<false>;
+	type RequireActiveValidatorForNomination = ConstBool<false>;
+	type SlashOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxNominatorsPerValidator = ConstU32<MAX_NOMINATORS_PER_VALIDATOR>;
+	type MinProvingEras = ConstU32<MIN_PROVING_ERAS>;
+	type SlashGracePeriod = ConstU32<SLASH_GRACE_PERIOD_ERAS>;
+	type UnbondingPeriod = ConstU32<UNBONDING_PERIOD_ERAS>;
+	type MaxUnbondingChunks = ConstU32<MAX_UNBONDING_CHUNKS>;
+	type MinClaimInterval = ConstU32<MIN_CLAIM_INTERVAL>;
+	type MaxValidators = ConstU32<MAX_VALIDATORS>;
+	type MaxNewValidatorsPerEra = ConstU32<MAX_NEW_VALIDATORS_PER_ERA>;
+	type HistoryDepth = ConstU32<HISTORY_DEPTH_ERAS>;
+	type RequireWhitelist = ConstBool<false>;
+	type MaxZeroStakeEras = ConstU32<MAX_ZERO_STAKE_ERAS>;
+	type TreasuryCut = TreasuryCut;
+	type TreasuryAccount = TreasuryAccount;
+	type DustHandling = DustHandling;
+	type EraDuration = ConstU32<ERA_DURATION>;
+	type ValidatorInflation = ValidatorInflation;
+	type NominatorInflation = NominatorInflation;
+	type MinNominators = ConstU32<MIN_NOMINATORS>;
+	type MaxLoyaltyBonus = MaxLoyaltyBonus;
+	type LoyaltyRampEras = ConstU32<LOYALTY_RAMP_ERAS>;
+	type MaxOffenceHistory = ConstU32<MAX_OFFENCE_HISTORY>;
+	type SoftCommissionCap = SoftCommissionCap;
+	type RedirectExcessCommission = ConstBool<true>;
+	type MinSelfStakeRatio = MinSelfStakeRatio;
+	type OnSlash = SlashToTreasury;
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the messaging pallet
 impl pallet_messaging::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type MessageDeposit = ConstU128<MESSAGE_DEPOSIT>;
+	type GroupDeposit = ConstU128<GROUP_DEPOSIT>;
 	type MaxMessageLength = ConstU32<1024>;
+	type MaxGroupCidLength = ConstU32<64>;
+	type MaxAttachments = ConstU32<10>;
+	type MaxInboxSize = ConstU32<100>;
+	type MaxOutboxSize = ConstU32<100>;
+	type MaxTotalMessages = ConstU32<MAX_TOTAL_MESSAGES>;
+	type MaxGroupMembers = ConstU32<50>;
+	type MaxGroupsPerUser = ConstU32<50>;
+	type MaxGroupMessages = ConstU32<1000>;
+	type MaxReactionsPerMessage = ConstU32<64>;
+	type MaxForwardDepth = ConstU32<16>;
 	type MessageTTL = ConstU32<10_000>;
+	type MinMessageTTL = ConstU32<100>;
+	type MaxMessageTTL = ConstU32<100_000>;
+	type MaxScheduleDelay = ConstU32<100_000>;
 	type WeightInfo = pallet_messaging::weights::SubstrateWeight<Runtime>;
 }
\ No newline at end of file
@@ -73,7 +73,8 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	// 101 changes the messaging storage layout without a migration, so it needs a fresh genesis.
+	spec_version: 101,
 	impl_version: 1,
 	apis: apis::RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -105,12 +106,40 @@ pub const BLOCK_HASH_COUNT: BlockNumber = 2400;
 // Messaging pallet constants
 pub const MAX_MESSAGE_LENGTH: u32 = 1024;
 pub const MESSAGE_TTL: BlockNumber = 10_000;
+pub const MESSAGE_DEPOSIT: Balance = 10 * MILLI_UNIT;
+pub const GROUP_DEPOSIT: Balance = UNIT;
+pub const MAX_TOTAL_MESSAGES: u32 = 10_000_000;
 
 // Staking constants
 pub const MIN_VALIDATOR_STAKE: Balance = 1000 * UNIT;
 pub const MIN_NOMINATION: Balance = 100 * UNIT;
 pub const MAX_NOMINATIONS_PER_NOMINATOR: u32 = 16;
 pub const REWARD_RATE_PER_BLOCK: u32 = 1; // 0.01% per block
+pub const MAX_PENDING_REWARD: Balance = 1_000_000 * UNIT;
+pub const MAX_BATCH_SLASHES: u32 = 64;
+pub const MAX_REWARD_GROWTH_PERCENT: u32 = 10;
+pub const REWARD_START_ERA: u32 = 1;
+pub const MAX_ACTIVE_VALIDATORS: u32 = 100;
+pub const MAX_VALIDATORS: u32 = 1_000;
+pub const MAX_NEW_VALIDATORS_PER_ERA: u32 = 16;
+pub const HISTORY_DEPTH_ERAS: u32 = 84;
+pub const MAX_ZERO_STAKE_ERAS: u32 = 3;
+pub const TREASURY_CUT_PERCENT: u32 = 0;
+pub const ERA_DURATION: BlockNumber = DAYS;
+pub const VALIDATOR_INFLATION_PERCENT: u32 = 15;
+pub const NOMINATOR_INFLATION_PERCENT: u32 = 10;
+pub const MIN_NOMINATORS: u32 = 0;
+pub const MAX_LOYALTY_BONUS_PERCENT: u32 = 10;
+pub const LOYALTY_RAMP_ERAS: u32 = 365;
+pub const MAX_OFFENCE_HISTORY: u32 = 32;
+pub const SOFT_COMMISSION_CAP_PERCENT: u32 = 100;
+pub const MIN_SELF_STAKE_RATIO_PERCENT: u32 = 0;
+pub const MAX_NOMINATORS_PER_VALIDATOR: u32 = 512;
+pub const MIN_PROVING_ERAS: u32 = 1;
+pub const SLASH_GRACE_PERIOD_ERAS: u32 = 0;
+pub const UNBONDING_PERIOD_ERAS: u32 = 28;
+pub const MAX_UNBONDING_CHUNKS: u32 = 32;
+pub const MIN_CLAIM_INTERVAL: BlockNumber = 10 * MINUTES;
 
 // Unit = the base number of indivisible units for balances
 pub const UNIT: Balance = 1_000_000_000_000;